@@ -0,0 +1,403 @@
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
+use miette::{Context, IntoDiagnostic};
+use regex::Regex;
+
+use crate::linting::{check_section, is_pattern, AffixConf, Conf, Presence, Section};
+use crate::parsing::is_footer;
+
+/// Prompts for each part of a commit message, validating every answer against the configured
+/// rules, and returns the composed message.
+pub(crate) fn compose(config: &Conf) -> miette::Result<String> {
+    let theme = ColorfulTheme::default();
+
+    let commit_type = prompt_type(&theme, config)?;
+
+    // The scope and breaking change prompts depend on the overrides for the type
+    let type_config = config.for_type(&commit_type);
+
+    let scope = prompt_scope(&theme, &type_config, &commit_type)?;
+
+    let breaking = &type_config.commit_breaking;
+
+    let breaking_change = breaking.allowed
+        && Confirm::with_theme(&theme)
+            .with_prompt("Is this a breaking change?")
+            .default(false)
+            .interact()
+            .into_diagnostic()?;
+
+    let subject: String = Input::with_theme(&theme)
+        .with_prompt("Subject")
+        .validate_with(|subject: &String| {
+            validate(
+                header(&commit_type, &scope, breaking_change, subject),
+                config,
+                Section::Subject,
+            )
+        })
+        .interact_text()
+        .into_diagnostic()?;
+
+    let prefix = prompt_affix(
+        &theme,
+        config,
+        "Prefix",
+        &config.commit_header.prefix,
+        |prefix| {
+            affixed(
+                prefix,
+                header(&commit_type, &scope, breaking_change, &subject),
+                "",
+            )
+        },
+    )?;
+
+    let suffix = prompt_affix(
+        &theme,
+        config,
+        "Suffix",
+        &config.commit_header.suffix,
+        |suffix| {
+            affixed(
+                &prefix,
+                header(&commit_type, &scope, breaking_change, &subject),
+                suffix,
+            )
+        },
+    )?;
+
+    let header = affixed(
+        &prefix,
+        header(&commit_type, &scope, breaking_change, &subject),
+        &suffix,
+    );
+
+    let body: String = Input::with_theme(&theme)
+        .with_prompt("Body")
+        .allow_empty(true)
+        .validate_with(|body: &String| validate(message(&header, body, &[]), config, Section::Body))
+        .interact_text()
+        .into_diagnostic()?;
+
+    let mut footers = Vec::new();
+
+    if breaking_change {
+        let token = config
+            .commit_footer
+            .breaking_token
+            .map_or("BREAKING CHANGE", |token| token.as_str());
+
+        let description: String = Input::with_theme(&theme)
+            .with_prompt(if breaking.require_footer {
+                "Describe the breaking change"
+            } else {
+                "Describe the breaking change (optional)"
+            })
+            .allow_empty(true)
+            .validate_with(|description: &String| {
+                let mut message = header.clone();
+
+                if !description.is_empty() {
+                    message.push_str(&format!("\n\n{token}: {description}"));
+                }
+
+                validate(message, config, Section::Breaking)
+            })
+            .interact_text()
+            .into_diagnostic()?;
+
+        if !description.is_empty() {
+            footers.push(format!("{token}: {description}"));
+        }
+    }
+
+    let grammar = config.grammar();
+
+    loop {
+        let footer: String = Input::with_theme(&theme)
+            .with_prompt("Footer, e.g. `Refs: #123` (leave empty to finish)")
+            .allow_empty(true)
+            .validate_with(|footer: &String| {
+                if footer.is_empty() {
+                    // Finishing, so the footers must be complete
+                    validate(message(&header, &body, &footers), config, Section::Footer)
+                } else if is_footer(footer, &grammar) {
+                    Ok(())
+                } else {
+                    Err("A footer is formatted as `Key: value` or `Key #value`".to_string())
+                }
+            })
+            .interact_text()
+            .into_diagnostic()?;
+
+        if footer.is_empty() {
+            break;
+        }
+
+        footers.push(footer);
+    }
+
+    Ok(message(&header, &body, &footers))
+}
+
+/// Joins the header, the body and the footers, leaving out the empty ones.
+fn message(header: &str, body: &str, footers: &[String]) -> String {
+    let mut message = header.to_string();
+
+    if !body.is_empty() {
+        message.push_str(&format!("\n\n{body}"));
+    }
+
+    if !footers.is_empty() {
+        message.push_str(&format!("\n\n{}", footers.join("\n")));
+    }
+
+    message
+}
+
+/// Runs `git commit` with the message.
+pub(crate) fn commit(message: &str) -> miette::Result<()> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new("git")
+        .args(["commit", "-F", "-"])
+        .stdin(Stdio::piped())
+        .spawn()
+        .into_diagnostic()
+        .wrap_err("Failed to run `git commit`")?;
+
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(message.as_bytes())
+        .into_diagnostic()
+        .wrap_err("Failed to pass the message to `git commit`")?;
+
+    let status = child.wait().into_diagnostic()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        miette::bail!("`git commit` exited with {status}")
+    }
+}
+
+fn prompt_type(theme: &ColorfulTheme, config: &Conf) -> miette::Result<String> {
+    let types = &config.commit_type.types;
+
+    if types.is_empty() || types.iter().any(|commit_type| is_pattern(commit_type)) {
+        Input::with_theme(theme)
+            .with_prompt("Type")
+            .validate_with(|commit_type: &String| {
+                validate(
+                    header(commit_type, "", false, "subject"),
+                    config,
+                    Section::Type,
+                )
+            })
+            .interact_text()
+            .into_diagnostic()
+    } else {
+        let index = Select::with_theme(theme)
+            .with_prompt("Type")
+            .items(types)
+            .default(0)
+            .interact()
+            .into_diagnostic()?;
+
+        Ok(types[index].clone())
+    }
+}
+
+fn prompt_scope(theme: &ColorfulTheme, config: &Conf, commit_type: &str) -> miette::Result<String> {
+    let scopes = config
+        .commit_scope
+        .scopes
+        .allowed(commit_type)
+        .filter(|scopes| !scopes.is_empty() && !scopes.iter().any(|scope| is_pattern(scope)));
+
+    let Some(scopes) = scopes else {
+        return Input::with_theme(theme)
+            .with_prompt("Scope")
+            .allow_empty(true)
+            .validate_with(|scope: &String| {
+                validate(
+                    header(commit_type, scope, false, "subject"),
+                    config,
+                    Section::Scope,
+                )
+            })
+            .interact_text()
+            .into_diagnostic();
+    };
+
+    let mut items = scopes.to_vec();
+
+    if !config.commit_scope.required {
+        items.insert(0, "(none)".to_string());
+    }
+
+    let index = Select::with_theme(theme)
+        .with_prompt("Scope")
+        .items(&items)
+        .default(0)
+        .interact()
+        .into_diagnostic()?;
+
+    if config.commit_scope.required {
+        Ok(items[index].clone())
+    } else if index == 0 {
+        Ok(String::new())
+    } else {
+        Ok(items[index].clone())
+    }
+}
+
+/// Prompts for the prefix or suffix of the header, when a pattern is configured for it. `message`
+/// builds the message to validate the answer with.
+fn prompt_affix(
+    theme: &ColorfulTheme,
+    config: &Conf,
+    name: &str,
+    affix: &AffixConf,
+    message: impl Fn(&str) -> String,
+) -> miette::Result<String> {
+    let Some(pattern) = &affix.pattern else {
+        return Ok(String::new());
+    };
+
+    if affix.presence == Presence::Forbidden {
+        return Ok(String::new());
+    }
+
+    // Invalid patterns are reported when the configuration is loaded
+    let regex = Regex::new(&format!("^(?:{pattern})$")).into_diagnostic()?;
+
+    Input::with_theme(theme)
+        .with_prompt(if affix.presence == Presence::Required {
+            name.to_string()
+        } else {
+            format!("{name} (optional)")
+        })
+        .allow_empty(true)
+        .validate_with(|value: &String| {
+            if !value.is_empty() && !regex.is_match(value) {
+                return Err(format!(
+                    "The {} must match `{pattern}`",
+                    name.to_lowercase()
+                ));
+            }
+
+            validate(message(value), config, Section::Header)
+        })
+        .interact_text()
+        .into_diagnostic()
+}
+
+/// The header with the prefix and suffix, each separated from it by a space.
+fn affixed(prefix: &str, header: String, suffix: &str) -> String {
+    [prefix, &header, suffix]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn header(commit_type: &str, scope: &str, breaking_change: bool, subject: &str) -> String {
+    let mut header = commit_type.to_string();
+
+    if !scope.is_empty() {
+        header.push_str(&format!("({scope})"));
+    }
+
+    if breaking_change {
+        header.push('!');
+    }
+
+    header.push_str(&format!(": {subject}"));
+    header
+}
+
+fn validate(message: String, config: &Conf, section: Section) -> Result<(), String> {
+    check_section(message.leak(), config, section)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header() {
+        assert_eq!(header("fix", "", false, "subject"), "fix: subject");
+        assert_eq!(header("feat", "ui", true, "subject"), "feat(ui)!: subject");
+    }
+
+    #[test]
+    fn test_affixed() {
+        assert_eq!(affixed("", "fix: subject".to_string(), ""), "fix: subject");
+        assert_eq!(
+            affixed("PROJ-1", "fix: subject".to_string(), "(#2)"),
+            "PROJ-1 fix: subject (#2)"
+        );
+    }
+
+    #[test]
+    fn test_validate_header() {
+        let mut config = Conf::default();
+        config.commit_header.prefix.pattern = Some("[A-Z]+-[0-9]+".to_string());
+        config.commit_header.prefix.presence = Presence::Required;
+
+        let header = || header("fix", "", false, "subject");
+
+        assert!(validate(affixed("", header(), ""), &config, Section::Header).is_err());
+        assert!(validate(affixed("PROJ-1", header(), ""), &config, Section::Header).is_ok());
+    }
+
+    #[test]
+    fn test_message() {
+        assert_eq!(message("fix: subject", "", &[]), "fix: subject");
+        assert_eq!(
+            message("fix: subject", "body", &["Refs: #1".to_string()]),
+            "fix: subject\n\nbody\n\nRefs: #1"
+        );
+    }
+
+    #[test]
+    fn test_validate_footer() {
+        let mut config = Conf::default();
+        config.commit_footer.required = vec!["Refs".to_string()];
+
+        assert!(validate(message("fix: subject", "", &[]), &config, Section::Footer).is_err());
+        assert!(validate(
+            message("fix: subject", "", &["Refs: #1".to_string()]),
+            &config,
+            Section::Footer
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_validate_type() {
+        let mut config = Conf::default();
+        config.commit_type.min_length = 4;
+
+        assert!(validate(header("fix", "", false, "subject"), &config, Section::Type).is_err());
+        assert!(validate(
+            header("refactor", "", false, "subject"),
+            &config,
+            Section::Type
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_validate_ignores_other_sections() {
+        let mut config = Conf::default();
+        config.commit_scope.required = true;
+
+        assert!(validate(header("fix", "", false, "subject"), &config, Section::Type).is_ok());
+        assert!(validate(header("fix", "", false, "subject"), &config, Section::Scope).is_err());
+    }
+}
@@ -0,0 +1,355 @@
+use std::{fs, path::Path};
+
+use miette::{Context, IntoDiagnostic};
+use serde_json::Value;
+
+use crate::linting::literal_entry;
+
+use super::overrides::insert;
+
+/// The result of translating a commitlint configuration.
+#[derive(Debug)]
+pub(crate) struct Import {
+    /// The translated configuration, as TOML.
+    pub(crate) config: String,
+    /// Descriptions of the rules and settings that could not be translated.
+    pub(crate) unmapped: Vec<String>,
+}
+
+/// Translates a commitlint configuration file in JSON, YAML or TOML format.
+pub(crate) fn import_commitlint(path: &Path) -> miette::Result<Import> {
+    let input = fs::read_to_string(path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to read `{}`", path.display()))?;
+
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+    let value = match extension {
+        "json" => serde_json::from_str::<Value>(&input).into_diagnostic(),
+        "yaml" | "yml" => serde_yaml::from_str::<Value>(&input).into_diagnostic(),
+        "toml" => toml::from_str::<Value>(&input).into_diagnostic(),
+        // YAML is a superset of JSON, so it covers extensionless `.commitlintrc` files as well
+        _ => serde_yaml::from_str::<Value>(&input).into_diagnostic(),
+    }
+    .wrap_err_with(|| format!("Failed to parse `{}`", path.display()))?;
+
+    let (table, unmapped) = translate(&value);
+
+    let config = toml::to_string_pretty(&table)
+        .into_diagnostic()
+        .wrap_err("An error occurred while serializing the configuration.")?;
+
+    Ok(Import { config, unmapped })
+}
+
+fn translate(value: &Value) -> (toml::Table, Vec<String>) {
+    let mut table = toml::Table::new();
+    let mut unmapped = Vec::new();
+
+    let extends = match value.get("extends") {
+        Some(Value::String(extends)) => vec![extends.as_str()],
+        Some(Value::Array(extends)) => extends.iter().filter_map(Value::as_str).collect(),
+        _ => vec![],
+    };
+
+    let mut presets = Vec::new();
+
+    for extends in extends {
+        match preset(extends) {
+            Some(preset) => presets.push(toml::Value::from(preset)),
+            None => unmapped.push(format!("extends `{extends}`")),
+        }
+    }
+
+    if !presets.is_empty() {
+        table.insert("extends".to_string(), toml::Value::Array(presets));
+    }
+
+    let rules = value.get("rules").and_then(Value::as_object);
+
+    for (name, rule) in rules.into_iter().flatten() {
+        let Some((level, always, value)) = parse_rule(rule) else {
+            unmapped.push(format!("`{name}`: unsupported rule configuration"));
+            continue;
+        };
+
+        if level == 0 {
+            continue;
+        }
+
+        match translate_rule(name, always, value) {
+            Ok(entries) => {
+                for (key, value) in entries {
+                    insert(&mut table, key, value);
+                }
+            }
+            Err(reason) => unmapped.push(format!("`{name}`: {reason}")),
+        }
+    }
+
+    if table.is_empty() {
+        unmapped.push(match rules {
+            Some(_) => "anything, as no enabled rule or preset has an equivalent".to_string(),
+            None => "anything, as there is no `rules` table".to_string(),
+        });
+    }
+
+    (table, unmapped)
+}
+
+/// Maps a commitlint shareable configuration to a built-in preset.
+fn preset(extends: &str) -> Option<&'static str> {
+    match extends {
+        "@commitlint/config-conventional" => Some("conventional"),
+        "@commitlint/config-angular" => Some("angular"),
+        _ => None,
+    }
+}
+
+/// Reads a rule in the `[level, 'always' | 'never', value]` form. The applicability defaults to
+/// `always`, and the value is optional.
+fn parse_rule(rule: &Value) -> Option<(u64, bool, Option<&Value>)> {
+    let rule = rule.as_array()?;
+
+    let level = rule.first()?.as_u64()?;
+
+    let always = match rule.get(1).map(Value::as_str) {
+        None | Some(Some("always")) => true,
+        Some(Some("never")) => false,
+        Some(_) => return None,
+    };
+
+    Some((level, always, rule.get(2)))
+}
+
+fn translate_rule(
+    name: &str,
+    always: bool,
+    value: Option<&Value>,
+) -> Result<Vec<(&'static str, toml::Value)>, &'static str> {
+    let entry = match (name, always) {
+        ("type-enum", true) => ("type.enum", strings(value)?),
+        ("type-case", true) => ("type.case", casing(value)?),
+        ("type-min-length", true) => ("type.min-length", min_length(value)?),
+        ("type-max-length", true) => ("type.max-length", max_length(value)?),
+        ("scope-enum", true) => ("scope.enum", strings(value)?),
+        ("scope-case", true) => ("scope.case", casing(value)?),
+        ("scope-min-length", true) => ("scope.min-length", min_length(value)?),
+        ("scope-max-length", true) => ("scope.max-length", max_length(value)?),
+        ("scope-empty", false) => ("scope.required", true.into()),
+        ("scope-empty", true) => return Err("forbidding a scope is not supported"),
+        ("subject-case", true) => ("subject.case", casing(value)?),
+        ("subject-full-stop", false) => ("subject.full-stop", full_stop(value)?),
+        ("subject-full-stop", true) => return Err("requiring a full stop is not supported"),
+        (_, false) if is_supported(name) => return Err("`never` is not supported for this rule"),
+        _ => return Err("no equivalent rule"),
+    };
+
+    Ok(vec![entry])
+}
+
+fn is_supported(name: &str) -> bool {
+    [
+        "type-enum",
+        "type-case",
+        "type-min-length",
+        "type-max-length",
+        "scope-enum",
+        "scope-case",
+        "scope-min-length",
+        "scope-max-length",
+        "subject-case",
+    ]
+    .contains(&name)
+}
+
+/// Translates a list of allowed values. commitlint matches them literally, so values that would
+/// be read as patterns are escaped.
+fn strings(value: Option<&Value>) -> Result<toml::Value, &'static str> {
+    value
+        .and_then(Value::as_array)
+        .and_then(|values| {
+            values
+                .iter()
+                .map(|value| value.as_str().map(|value| literal_entry(value).into()))
+                .collect::<Option<Vec<_>>>()
+        })
+        .map(toml::Value::Array)
+        .ok_or("expected a list of strings")
+}
+
+/// Translates a minimum length. commitlint accepts values of exactly the minimum length, while
+/// `min-length` rejects them, so the limit is lowered by one.
+fn min_length(value: Option<&Value>) -> Result<toml::Value, &'static str> {
+    length(value, |length| length.saturating_sub(1))
+}
+
+/// Translates a maximum length. commitlint accepts values of exactly the maximum length, while
+/// `max-length` rejects them, so the limit is raised by one.
+fn max_length(value: Option<&Value>) -> Result<toml::Value, &'static str> {
+    length(value, |length| length.saturating_add(1))
+}
+
+fn length(value: Option<&Value>, adjust: fn(u64) -> u64) -> Result<toml::Value, &'static str> {
+    value
+        .and_then(Value::as_u64)
+        .and_then(|length| i64::try_from(adjust(length)).ok())
+        .map(toml::Value::Integer)
+        .ok_or("expected a number")
+}
+
+/// Translates a case, or a list of cases.
+fn casing(value: Option<&Value>) -> Result<toml::Value, &'static str> {
+    match value {
+        Some(Value::Array(values)) => values
+            .iter()
+            .map(|value| casing(Some(value)))
+            .collect::<Result<Vec<_>, _>>()
+            .map(toml::Value::Array),
+        Some(Value::String(value)) => {
+            let casing = match value.as_str() {
+                "camel-case" => "camel",
+                "kebab-case" => "kebab",
+                "pascal-case" => "pascal",
+                "snake-case" => "snake",
+                "lower-case" | "lowercase" => "lower",
+                "upper-case" | "uppercase" => "upper",
+                "sentence-case" | "sentencecase" => "sentence",
+                "start-case" => "start",
+                _ => return Err("unsupported case"),
+            };
+
+            Ok(casing.into())
+        }
+        _ => Err("expected a case or a list of cases"),
+    }
+}
+
+fn full_stop(value: Option<&Value>) -> Result<toml::Value, &'static str> {
+    match value.and_then(Value::as_str) {
+        None | Some(".") => Ok(false.into()),
+        Some(_) => Err("only `.` is supported as full stop"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_translate() {
+        let (table, unmapped) = translate(&json!({
+            "extends": ["@commitlint/config-conventional"],
+            "rules": {
+                "type-enum": [2, "always", ["feat", "fix"]],
+                "scope-case": [2, "always", "snake-case"],
+                "scope-empty": [2, "never"],
+                "subject-full-stop": [2, "never", "."],
+                "type-max-length": [1, "always", 10],
+                "scope-min-length": [2, "always", 0],
+            }
+        }));
+
+        assert!(unmapped.is_empty(), "{unmapped:?}");
+        assert_eq!(
+            table,
+            toml::toml! {
+                extends = ["conventional"]
+
+                [type]
+                enum = ["feat", "fix"]
+                max-length = 11
+
+                [scope]
+                case = "snake"
+                min-length = 0
+                required = true
+
+                [subject]
+                full-stop = false
+            }
+        );
+    }
+
+    #[test]
+    fn test_translate_reports_unmapped() {
+        let (table, unmapped) = translate(&json!({
+            "extends": ["@company/commitlint-config"],
+            "rules": {
+                "header-max-length": [2, "always", 72],
+                "type-enum": [2, "never", ["wip"]],
+                "type-case": [2, "never", "sentence-case"],
+            }
+        }));
+
+        assert!(table.is_empty());
+        assert_eq!(unmapped.len(), 5);
+    }
+
+    #[test]
+    fn test_translate_skips_disabled_rules() {
+        let (table, unmapped) = translate(&json!({
+            "rules": {
+                "header-max-length": [0, "always", 72],
+                "type-enum": [0, "always", ["feat"]],
+            }
+        }));
+
+        assert!(table.is_empty());
+        assert_eq!(
+            unmapped,
+            vec!["anything, as no enabled rule or preset has an equivalent"]
+        );
+    }
+
+    #[test]
+    fn test_translate_without_rules() {
+        let (table, unmapped) = translate(&json!({ "extends": ["@company/commitlint-config"] }));
+
+        assert!(table.is_empty());
+        assert_eq!(
+            unmapped,
+            vec![
+                "extends `@company/commitlint-config`",
+                "anything, as there is no `rules` table"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_translate_lengths() {
+        let (table, _) = translate(&json!({
+            "rules": {
+                "type-min-length": [2, "always", 3],
+                "scope-max-length": [2, "always", 20],
+            }
+        }));
+
+        assert_eq!(
+            table,
+            toml::toml! {
+                [type]
+                min-length = 2
+
+                [scope]
+                max-length = 21
+            }
+        );
+    }
+
+    #[test]
+    fn test_import_yaml() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(".commitlintrc.yaml");
+        fs::write(&file, "rules:\n  type-enum: [2, always, [feat, fix]]\n").unwrap();
+
+        let import = import_commitlint(&file).unwrap();
+
+        assert_eq!(
+            import.config,
+            "[type]\nenum = [\n    \"feat\",\n    \"fix\",\n]\n"
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use figment::{
+    providers::{Format, Json, Toml},
+    value::{Dict, Map},
+    Figment, Metadata, Profile, Provider,
+};
+use miette::{Context, IntoDiagnostic};
+
+use super::read_file;
+
+/// A configuration that ships with the binary, and can be extended by name.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Preset {
+    pub(crate) name: &'static str,
+    source: &'static str,
+}
+
+pub(crate) const PRESETS: [Preset; 3] = [
+    Preset {
+        name: "conventional",
+        source: include_str!("presets/conventional.toml"),
+    },
+    Preset {
+        name: "angular",
+        source: include_str!("presets/angular.toml"),
+    },
+    Preset {
+        name: "gitmoji",
+        source: include_str!("presets/gitmoji.toml"),
+    },
+];
+
+impl Provider for &Preset {
+    fn metadata(&self) -> Metadata {
+        Metadata::named(format!("`{}` preset", self.name))
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, figment::Error> {
+        Toml::string(self.source).data()
+    }
+}
+
+/// A source of configuration, in the order it is merged.
+#[derive(Debug, PartialEq)]
+pub(crate) enum ConfigSource {
+    Preset(&'static Preset),
+    /// A file, with the key path of the configuration inside it. The key path is empty for
+    /// dedicated configuration files, and points into the metadata of manifests like `Cargo.toml`.
+    File(PathBuf, &'static [&'static str]),
+}
+
+impl ConfigSource {
+    /// The source as figment provider.
+    pub(crate) fn provider(self) -> Figment {
+        match self {
+            ConfigSource::Preset(preset) => Figment::from(preset),
+            ConfigSource::File(path, key) => {
+                let figment = if path.extension().is_some_and(|e| e == "json") {
+                    Figment::from(Json::file(path))
+                } else {
+                    Figment::from(Toml::file(path))
+                };
+
+                match key {
+                    [] => figment,
+                    key => figment.focus(&key.join(".")),
+                }
+            }
+        }
+    }
+}
+
+impl Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigSource::Preset(preset) => write!(f, "the `{}` preset", preset.name),
+            ConfigSource::File(path, []) => write!(f, "`{}`", path.display()),
+            ConfigSource::File(path, key) => {
+                write!(f, "`{}` at `{}`", path.display(), key.join("."))
+            }
+        }
+    }
+}
+
+/// Expands the `extends` key of each source. Returns all sources from lowest to highest
+/// precedence, where every source comes directly after the sources it extends.
+pub(crate) fn resolve(sources: Vec<ConfigSource>) -> miette::Result<Vec<ConfigSource>> {
+    let mut resolved = Vec::new();
+
+    for source in sources {
+        resolve_source(source, &mut Vec::new(), &mut resolved)?;
+    }
+
+    Ok(resolved)
+}
+
+fn resolve_source(
+    source: ConfigSource,
+    stack: &mut Vec<PathBuf>,
+    resolved: &mut Vec<ConfigSource>,
+) -> miette::Result<()> {
+    let ConfigSource::File(file, key) = &source else {
+        resolved.push(source);
+        return Ok(());
+    };
+
+    if !file.is_file() {
+        resolved.push(source);
+        return Ok(());
+    }
+
+    let Some(config) = read_file(file, key)? else {
+        return Ok(());
+    };
+
+    let canonical = file
+        .canonicalize()
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to resolve `{}`", file.display()))?;
+
+    if stack.contains(&canonical) {
+        miette::bail!("`{}` extends itself", file.display());
+    }
+
+    stack.push(canonical);
+
+    let dir = file.parent().unwrap_or(Path::new("."));
+
+    for entry in config.extends {
+        if let Some(preset) = PRESETS.iter().find(|preset| preset.name == entry) {
+            resolved.push(ConfigSource::Preset(preset));
+        } else if is_path(&entry) {
+            resolve_source(ConfigSource::File(dir.join(&entry), &[]), stack, resolved)?;
+        } else {
+            miette::bail!(
+                help = format!(
+                    "Available presets are: {:?}. Paths must contain a `/` or end in `.toml`",
+                    PRESETS.iter().map(|preset| preset.name).collect::<Vec<_>>()
+                ),
+                "Unknown preset `{entry}` in `{}`",
+                file.display()
+            );
+        }
+    }
+
+    stack.pop();
+    resolved.push(source);
+
+    Ok(())
+}
+
+fn is_path(entry: &str) -> bool {
+    entry.contains('/') || entry.contains('\\') || entry.ends_with(".toml")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use figment::providers::Serialized;
+
+    use super::*;
+    use crate::{config::validate_conf, linting::Conf};
+
+    fn preset(name: &str) -> &'static Preset {
+        PRESETS.iter().find(|preset| preset.name == name).unwrap()
+    }
+
+    fn file(path: PathBuf) -> ConfigSource {
+        ConfigSource::File(path, &[])
+    }
+
+    #[test]
+    fn test_presets_are_valid() {
+        for preset in &PRESETS {
+            let config = Figment::new()
+                .merge(Serialized::defaults(Conf::default()))
+                .merge(preset)
+                .extract::<Conf>()
+                .unwrap();
+
+            assert!(validate_conf(&config).is_ok(), "{}", preset.name);
+        }
+    }
+
+    #[test]
+    fn test_json_file() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("config.json"),
+            "{\"scope\": {\"required\": true}}",
+        )
+        .unwrap();
+
+        let config = Figment::new()
+            .merge(Serialized::defaults(Conf::default()))
+            .merge(file(dir.path().join("config.json")).provider())
+            .extract::<Conf>()
+            .unwrap();
+
+        assert!(config.commit_scope.required);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("shared")).unwrap();
+        fs::write(
+            dir.path().join("shared/base.toml"),
+            "extends = [\"angular\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join(".change-scribe.toml"),
+            "extends = [\"conventional\", \"./shared/base.toml\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            resolve(vec![file(dir.path().join(".change-scribe.toml"))]).unwrap(),
+            vec![
+                ConfigSource::Preset(preset("conventional")),
+                ConfigSource::Preset(preset("angular")),
+                file(dir.path().join("./shared/base.toml")),
+                file(dir.path().join(".change-scribe.toml")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"crate\"\n\n[package.metadata.change-scribe]\nextends = [\"angular\"]\n",
+        )
+        .unwrap();
+
+        let manifest = ConfigSource::File(
+            dir.path().join("Cargo.toml"),
+            &["package", "metadata", "change-scribe"],
+        );
+
+        assert_eq!(
+            resolve(vec![manifest]).unwrap(),
+            vec![
+                ConfigSource::Preset(preset("angular")),
+                ConfigSource::File(
+                    dir.path().join("Cargo.toml"),
+                    &["package", "metadata", "change-scribe"]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_unknown_preset() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(".change-scribe.toml"),
+            "extends = [\"unknown\"]\n",
+        )
+        .unwrap();
+
+        assert!(resolve(vec![file(dir.path().join(".change-scribe.toml"))]).is_err());
+    }
+
+    #[test]
+    fn test_resolve_cycle() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.toml"), "extends = [\"b.toml\"]\n").unwrap();
+        fs::write(dir.path().join("b.toml"), "extends = [\"a.toml\"]\n").unwrap();
+
+        assert!(resolve(vec![file(dir.path().join("a.toml"))]).is_err());
+    }
+}
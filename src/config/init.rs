@@ -0,0 +1,287 @@
+use std::{collections::HashMap, fmt::Write, process::Command};
+
+use miette::{Context, IntoDiagnostic};
+
+use crate::{
+    linting::{literal_entry, Casing},
+    parsing::parse,
+};
+
+/// Share of commits that need a scope before `scope.required` is suggested.
+const REQUIRED_SCOPE_THRESHOLD: f64 = 0.9;
+
+/// Generates a starter configuration from the commit history of the repository in the current
+/// directory.
+pub(crate) fn init() -> miette::Result<String> {
+    let output = Command::new("git")
+        .args(["log", "--format=%B%x00"])
+        .output()
+        .into_diagnostic()
+        .wrap_err("Failed to run `git log`")?;
+
+    if !output.status.success() {
+        miette::bail!(
+            "`git log` exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let history = String::from_utf8_lossy(&output.stdout);
+
+    let messages = history
+        .split('\0')
+        .map(str::trim)
+        .filter(|message| !message.is_empty())
+        .map(|message| &*message.to_string().leak())
+        .collect::<Vec<_>>();
+
+    generate(&messages)
+}
+
+fn generate(messages: &[&'static str]) -> miette::Result<String> {
+    let mut types = HashMap::<&str, usize>::new();
+    let mut scopes = HashMap::<&str, usize>::new();
+    let mut conventional = 0;
+    let mut scoped = 0;
+
+    for commit in messages.iter().filter_map(|message| parse(message).ok()) {
+        conventional += 1;
+        *types.entry(commit.commit_type).or_default() += 1;
+
+        if !commit.scope.is_empty() {
+            scoped += 1;
+        }
+
+        for scope in commit.scope {
+            *scopes.entry(scope).or_default() += 1;
+        }
+    }
+
+    if conventional == 0 {
+        miette::bail!(
+            "None of the {} commits in the history follow the conventional commit format",
+            messages.len()
+        );
+    }
+
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "# Generated by `change-scribe config init` from {} commits, of which {conventional} follow",
+        messages.len()
+    )
+    .into_diagnostic()?;
+    writeln!(output, "# the conventional commit format.").into_diagnostic()?;
+
+    writeln!(output, "\n[type]").into_diagnostic()?;
+    write_enum(&mut output, "type", &types)?;
+
+    writeln!(output, "\n[scope]").into_diagnostic()?;
+
+    let has_scope_enum = if scopes.is_empty() {
+        writeln!(output, "# No scopes are in use").into_diagnostic()?;
+        false
+    } else {
+        write_enum(&mut output, "scope", &scopes)?
+    };
+
+    let share = scoped as f64 / conventional as f64;
+    writeln!(
+        output,
+        "# {:.0}% of the commits have a scope",
+        share * 100.0
+    )
+    .into_diagnostic()?;
+
+    if share >= REQUIRED_SCOPE_THRESHOLD && has_scope_enum {
+        writeln!(output, "required = true").into_diagnostic()?;
+    } else {
+        writeln!(output, "# required = true").into_diagnostic()?;
+    }
+
+    Ok(output)
+}
+
+/// Writes the `enum` and `case` keys for the values in use. Values that are not in the dominant
+/// casing are left out, and listed in a comment instead. Returns whether the keys were written,
+/// which they are not if no value is left, as an empty `enum` would reject every commit.
+fn write_enum(
+    output: &mut String,
+    name: &str,
+    frequencies: &HashMap<&str, usize>,
+) -> miette::Result<bool> {
+    let mut values = frequencies.iter().collect::<Vec<_>>();
+    values.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+
+    let case = dominant_casing(frequencies);
+
+    if !values.iter().any(|(value, _)| case.matches(value)) {
+        writeln!(
+            output,
+            "# {}s in use, by number of commits: {}",
+            capitalize(name),
+            frequencies_list(&values)
+        )
+        .into_diagnostic()?;
+        writeln!(
+            output,
+            "# Not restricted, because none of them share a casing"
+        )
+        .into_diagnostic()?;
+
+        return Ok(false);
+    }
+
+    let (matching, other) = values
+        .into_iter()
+        .partition::<Vec<_>, _>(|(value, _)| case.matches(value));
+
+    writeln!(
+        output,
+        "# {}s in use, by number of commits: {}",
+        capitalize(name),
+        frequencies_list(&matching)
+    )
+    .into_diagnostic()?;
+
+    if !other.is_empty() {
+        writeln!(
+            output,
+            "# Left out because they are not in {case}: {}",
+            frequencies_list(&other)
+        )
+        .into_diagnostic()?;
+    }
+
+    let values = matching
+        .iter()
+        .map(|(value, _)| toml::Value::from(literal_entry(value)))
+        .collect::<Vec<_>>();
+
+    writeln!(output, "enum = {}", toml::Value::Array(values)).into_diagnostic()?;
+    writeln!(
+        output,
+        "case = {}",
+        toml::Value::try_from(case).into_diagnostic()?
+    )
+    .into_diagnostic()?;
+
+    Ok(true)
+}
+
+/// The casing most values are in, weighted by their frequency. Ties are resolved in favor of the
+/// default casing, and then of the casing listed first in [`Casing::ALL`]. `any` is never chosen.
+fn dominant_casing(frequencies: &HashMap<&str, usize>) -> Casing {
+    let score = |casing: &Casing| {
+        frequencies
+            .iter()
+            .filter(|(value, _)| casing.matches(value))
+            .map(|(_, count)| count)
+            .sum::<usize>()
+    };
+
+    Casing::ALL
+        .iter()
+        .rev()
+        .copied()
+        .filter(|casing| *casing != Casing::Any)
+        .max_by_key(|casing| (score(casing), *casing == Casing::default()))
+        .unwrap_or_default()
+}
+
+fn frequencies_list(values: &[(&&str, &usize)]) -> String {
+    values
+        .iter()
+        .map(|(value, count)| format!("{value} ({count})"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn capitalize(input: &str) -> String {
+    let mut chars = input.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linting::Conf;
+
+    #[test]
+    fn test_generate() {
+        let output = generate(&[
+            "feat(ui): add button",
+            "feat(ui): add dialog",
+            "fix(core): resolve crash",
+            "Fix(Core): resolve other crash",
+            "Merge branch 'main'",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            output,
+            "# Generated by `change-scribe config init` from 5 commits, of which 4 follow\n\
+             # the conventional commit format.\n\
+             \n\
+             [type]\n\
+             # Types in use, by number of commits: feat (2), fix (1)\n\
+             # Left out because they are not in kebab-case: Fix (1)\n\
+             enum = [\"feat\", \"fix\"]\n\
+             case = \"kebab\"\n\
+             \n\
+             [scope]\n\
+             # Scopes in use, by number of commits: ui (2), core (1)\n\
+             # Left out because they are not in kebab-case: Core (1)\n\
+             enum = [\"ui\", \"core\"]\n\
+             case = \"kebab\"\n\
+             # 100% of the commits have a scope\n\
+             required = true\n"
+        );
+
+        assert!(toml::from_str::<Conf>(&output).is_ok());
+    }
+
+    #[test]
+    fn test_generate_without_scopes() {
+        let output = generate(&["feat: add button", "fix: resolve crash"]).unwrap();
+
+        assert!(output.contains("# No scopes are in use\n"));
+        assert!(output.contains("# required = true\n"));
+
+        let config = toml::from_str::<Conf>(&output).unwrap();
+        assert!(crate::config::validate_conf(&config).is_ok());
+    }
+
+    #[test]
+    fn test_generate_without_scopes_in_a_casing() {
+        let output = generate(&[
+            "feat(ui Core_x): add button",
+            "fix(ui Core_x): resolve crash",
+        ])
+        .unwrap();
+
+        assert!(output.contains("# Not restricted, because none of them share a casing\n"));
+        assert!(output.contains("# required = true\n"));
+
+        let config = toml::from_str::<Conf>(&output).unwrap();
+        assert!(crate::config::validate_conf(&config).is_ok());
+    }
+
+    #[test]
+    fn test_generate_without_conventional_commits() {
+        assert!(generate(&["Initial commit"]).is_err());
+    }
+
+    #[test]
+    fn test_dominant_casing() {
+        let frequencies = HashMap::from([("userSettings", 3), ("user-settings", 1)]);
+
+        assert_eq!(dominant_casing(&frequencies), Casing::Camel);
+    }
+}
@@ -0,0 +1,315 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use figment::{
+    providers::{Env, Serialized},
+    Figment,
+};
+use miette::{Context, IntoDiagnostic};
+
+use crate::linting::Conf;
+
+pub(crate) use commitlint::import_commitlint;
+use extends::{resolve, ConfigSource};
+pub(crate) use init::init;
+use overrides::{parse_override, Overrides};
+pub(crate) use schema::schema;
+pub(crate) use show::show;
+pub(crate) use validate::{read_file, validate_conf};
+use workspace::expand_scopes;
+
+mod commitlint;
+mod extends;
+mod init;
+mod overrides;
+mod schema;
+mod show;
+mod validate;
+mod workspace;
+
+/// Names of the project configuration files, from lowest to highest precedence.
+const CONFIG_FILES: [&str; 2] = ["change-scribe.toml", ".change-scribe.toml"];
+
+/// Manifests that can embed the configuration, with the key path of the configuration inside them.
+/// These take precedence in this order, but come before the configuration files in the same
+/// directory.
+const MANIFESTS: [(&str, &[&str]); 4] = [
+    ("package.json", &["change-scribe"]),
+    ("pyproject.toml", &["tool", "change-scribe"]),
+    ("Cargo.toml", &["workspace", "metadata", "change-scribe"]),
+    ("Cargo.toml", &["package", "metadata", "change-scribe"]),
+];
+
+/// Prefix of the environment variables that override the configuration.
+const ENV_PREFIX: &str = "CHANGE_SCRIBE_";
+
+/// Command line arguments that control how the configuration is loaded.
+#[derive(Debug, clap::Args)]
+pub(crate) struct ConfigArgs {
+    #[clap(short, long, global = true)]
+    /// Path to the configuration file. Overrides the default configuration.
+    config: Option<PathBuf>,
+    #[clap(long = "set", global = true, value_name = "KEY=VALUE", value_parser = parse_override)]
+    /// Override a configuration value, e.g. `--set scope.required=true`. Can be repeated.
+    overrides: Vec<(String, toml::Value)>,
+    #[clap(short, long, global = true)]
+    /// Print which configuration files are used.
+    verbose: bool,
+}
+
+pub(crate) fn load_config(args: &ConfigArgs) -> miette::Result<Conf> {
+    extract(&figment(args)?)
+}
+
+/// Collects all configuration sources, without extracting the configuration from them.
+///
+/// Sources are merged in the following order, with later sources taking precedence: the defaults,
+/// the configuration files (each preceded by the configurations it extends), `CHANGE_SCRIBE_*`
+/// environment variables and `--set` flags.
+pub(crate) fn figment(args: &ConfigArgs) -> miette::Result<Figment> {
+    let sources = match &args.config {
+        Some(config_path) => vec![ConfigSource::File(config_path.clone(), &[])],
+        None => {
+            let cwd = env::current_dir()
+                .into_diagnostic()
+                .context("Failed to determine the current directory")?;
+
+            discover(&cwd, user_config().as_deref())?
+        }
+    };
+
+    let sources = resolve(sources)?;
+
+    if args.verbose {
+        if sources.is_empty() {
+            eprintln!("No configuration file found, using the default configuration");
+        }
+
+        for source in &sources {
+            eprintln!("Using configuration from {source}");
+        }
+    }
+
+    let figment = sources.into_iter().fold(
+        Figment::new().merge(Serialized::defaults(Conf::default())),
+        |config, source| config.merge(source.provider()),
+    );
+
+    Ok(figment
+        .merge(env_provider())
+        .merge(Overrides(args.overrides.clone())))
+}
+
+/// Reads `CHANGE_SCRIBE_*` environment variables, where `__` separates nested keys. For example,
+/// `CHANGE_SCRIBE_SCOPE__MIN_LENGTH` sets `scope.min-length`.
+fn env_provider() -> Env {
+    Env::prefixed(ENV_PREFIX)
+        .split("__")
+        .map(|key| key.as_str().replace('_', "-").into())
+}
+
+/// Extracts the configuration from the merged sources, reads the scopes from `scope.from`, and
+/// checks it for contradictions.
+pub(crate) fn extract(figment: &Figment) -> miette::Result<Conf> {
+    let mut config = figment
+        .extract::<Conf>()
+        .into_diagnostic()
+        .context("Failed to load configuration")?;
+
+    let cwd = env::current_dir()
+        .into_diagnostic()
+        .context("Failed to determine the current directory")?;
+
+    expand_scopes(&mut config, &cwd)?;
+
+    validate_conf(&config)?;
+
+    Ok(config)
+}
+
+/// Finds the configuration that applies to `start`, ordered from lowest to highest precedence.
+///
+/// The user configuration comes first, followed by the project configuration in the nearest
+/// directory containing one. Within a directory, manifests come before configuration files. The
+/// search stops at the root of the git repository.
+fn discover(start: &Path, user_config: Option<&Path>) -> miette::Result<Vec<ConfigSource>> {
+    let mut sources = user_config
+        .filter(|path| path.is_file())
+        .map(|path| ConfigSource::File(path.to_path_buf(), &[]))
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    for dir in start.ancestors() {
+        let mut found = Vec::new();
+
+        for (file, key) in MANIFESTS {
+            let path = dir.join(file);
+
+            if path.is_file() && read_file(&path, key)?.is_some() {
+                found.push(ConfigSource::File(path, key));
+            }
+        }
+
+        found.extend(
+            CONFIG_FILES
+                .iter()
+                .map(|file| dir.join(file))
+                .filter(|path| path.is_file())
+                .map(|path| ConfigSource::File(path, &[])),
+        );
+
+        if !found.is_empty() {
+            sources.extend(found);
+            break;
+        }
+
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+
+    Ok(sources)
+}
+
+/// Path of the user configuration, following the XDG base directory specification.
+fn user_config() -> Option<PathBuf> {
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_home.join("change-scribe").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn file(path: PathBuf) -> ConfigSource {
+        ConfigSource::File(path, &[])
+    }
+
+    #[test]
+    fn test_discover_in_parent() {
+        let root = tempfile::tempdir().unwrap();
+        let nested = root.path().join("crates/core");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(root.path().join(".change-scribe.toml"), "").unwrap();
+
+        assert_eq!(
+            discover(&nested, None).unwrap(),
+            vec![file(root.path().join(".change-scribe.toml"))]
+        );
+    }
+
+    #[test]
+    fn test_discover_nearest() {
+        let root = tempfile::tempdir().unwrap();
+        let nested = root.path().join("crates/core");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(root.path().join(".change-scribe.toml"), "").unwrap();
+        fs::write(nested.join("change-scribe.toml"), "").unwrap();
+
+        assert_eq!(
+            discover(&nested, None).unwrap(),
+            vec![file(nested.join("change-scribe.toml"))]
+        );
+    }
+
+    #[test]
+    fn test_discover_stops_at_git_root() {
+        let root = tempfile::tempdir().unwrap();
+        let repository = root.path().join("repository");
+        fs::create_dir_all(repository.join(".git")).unwrap();
+        fs::write(root.path().join(".change-scribe.toml"), "").unwrap();
+
+        assert!(discover(&repository, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_discover_user_config() {
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir_all(root.path().join(".git")).unwrap();
+        fs::write(root.path().join(".change-scribe.toml"), "").unwrap();
+
+        let user = tempfile::tempdir().unwrap();
+        let user_config = user.path().join("config.toml");
+        fs::write(&user_config, "").unwrap();
+
+        assert_eq!(
+            discover(root.path(), Some(&user_config)).unwrap(),
+            vec![
+                file(user_config),
+                file(root.path().join(".change-scribe.toml"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_discover_manifests() {
+        let root = tempfile::tempdir().unwrap();
+        fs::write(
+            root.path().join("Cargo.toml"),
+            "[workspace]\n\n[workspace.metadata.change-scribe]\nscope.required = true\n",
+        )
+        .unwrap();
+        fs::write(root.path().join(".change-scribe.toml"), "").unwrap();
+
+        assert_eq!(
+            discover(root.path(), None).unwrap(),
+            vec![
+                ConfigSource::File(
+                    root.path().join("Cargo.toml"),
+                    &["workspace", "metadata", "change-scribe"]
+                ),
+                file(root.path().join(".change-scribe.toml")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_discover_skips_manifests_without_configuration() {
+        let root = tempfile::tempdir().unwrap();
+        let member = root.path().join("crates/core");
+        fs::create_dir_all(&member).unwrap();
+        fs::write(member.join("Cargo.toml"), "[package]\nname = \"core\"\n").unwrap();
+        fs::write(
+            root.path().join("pyproject.toml"),
+            "[tool.change-scribe.scope]\nrequired = true\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            discover(&member, None).unwrap(),
+            vec![ConfigSource::File(
+                root.path().join("pyproject.toml"),
+                &["tool", "change-scribe"]
+            )]
+        );
+    }
+
+    #[test]
+    #[allow(clippy::result_large_err)]
+    fn test_env_provider() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("CHANGE_SCRIBE_SCOPE__REQUIRED", "true");
+            jail.set_env("CHANGE_SCRIBE_SCOPE__MIN_LENGTH", "2");
+            jail.set_env("CHANGE_SCRIBE_TYPE__ENUM", "[feat, fix]");
+
+            let config = Figment::new()
+                .merge(Serialized::defaults(Conf::default()))
+                .merge(env_provider())
+                .extract::<Conf>()?;
+
+            assert!(config.commit_scope.required);
+            assert_eq!(config.commit_scope.min_length, 2);
+            assert_eq!(*config.commit_type.types, ["feat", "fix"]);
+
+            Ok(())
+        });
+    }
+}
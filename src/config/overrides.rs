@@ -0,0 +1,110 @@
+use figment::{
+    providers::Serialized,
+    value::{Dict, Map},
+    Metadata, Profile, Provider,
+};
+
+/// Configuration values set with the `--set` flag.
+pub(crate) struct Overrides(pub(crate) Vec<(String, toml::Value)>);
+
+impl Provider for Overrides {
+    fn metadata(&self) -> Metadata {
+        Metadata::named("`--set` flag")
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, figment::Error> {
+        let mut table = toml::Table::new();
+
+        for (key, value) in &self.0 {
+            insert(&mut table, key, value.clone());
+        }
+
+        Serialized::defaults(table).data()
+    }
+}
+
+/// Inserts the value at the `.`-separated key path, creating intermediate tables as needed.
+pub(super) fn insert(table: &mut toml::Table, key: &str, value: toml::Value) {
+    match key.split_once('.') {
+        Some((head, rest)) => {
+            let entry = table
+                .entry(head)
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+
+            if !entry.is_table() {
+                *entry = toml::Value::Table(toml::Table::new());
+            }
+
+            if let toml::Value::Table(nested) = entry {
+                insert(nested, rest, value);
+            }
+        }
+        None => {
+            table.insert(key.to_string(), value);
+        }
+    }
+}
+
+/// Parses a `key=value` pair. The value is read as a TOML value, falling back to a plain string.
+pub(crate) fn parse_override(input: &str) -> Result<(String, toml::Value), String> {
+    let (key, value) = input
+        .split_once('=')
+        .ok_or_else(|| format!("expected `key=value`, found `{input}`"))?;
+
+    let value = toml::from_str::<toml::Table>(&format!("value = {value}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()));
+
+    Ok((key.trim().to_string(), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use figment::Figment;
+
+    use super::*;
+    use crate::linting::Conf;
+
+    #[test]
+    fn test_parse_toml_value() {
+        assert_eq!(
+            parse_override("type.enum=[\"feat\", \"fix\"]").unwrap(),
+            (
+                "type.enum".to_string(),
+                toml::Value::Array(vec!["feat".into(), "fix".into()])
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_plain_string() {
+        assert_eq!(
+            parse_override("scope.case=snake").unwrap(),
+            (
+                "scope.case".to_string(),
+                toml::Value::String("snake".into())
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_missing_value() {
+        assert!(parse_override("scope.required").is_err());
+    }
+
+    #[test]
+    fn test_overrides() {
+        let config = Figment::new()
+            .merge(Serialized::defaults(Conf::default()))
+            .merge(Overrides(vec![
+                parse_override("scope.required=true").unwrap(),
+                parse_override("scope.min-length=2").unwrap(),
+            ]))
+            .extract::<Conf>()
+            .unwrap();
+
+        assert!(config.commit_scope.required);
+        assert_eq!(config.commit_scope.min_length, 2);
+    }
+}
@@ -0,0 +1,36 @@
+use miette::{Context, IntoDiagnostic};
+
+use crate::linting::Conf;
+
+/// Generates the JSON Schema of the configuration file.
+pub(crate) fn schema() -> miette::Result<String> {
+    let schema = schemars::schema_for!(Conf);
+
+    serde_json::to_string_pretty(&schema)
+        .into_diagnostic()
+        .wrap_err("An error occurred while serializing the schema.")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+
+    #[test]
+    fn test_schema() {
+        let schema = serde_json::from_str::<Value>(&schema().unwrap()).unwrap();
+
+        assert_eq!(schema["additionalProperties"], Value::Bool(false));
+        assert_eq!(
+            schema["definitions"]["TypeConf"]["properties"]["enum"]["default"],
+            serde_json::json!(["*"])
+        );
+        assert_eq!(
+            schema["definitions"]["Casing"]["oneOf"]
+                .as_array()
+                .map(Vec::len),
+            Some(10)
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use std::fmt::Write;
+
+use figment::{Figment, Source};
+use miette::{Context, IntoDiagnostic};
+
+use crate::linting::Conf;
+
+/// Renders the configuration as TOML, annotating each value with the source it came from.
+pub(crate) fn show(config: &Conf, figment: &Figment) -> miette::Result<String> {
+    let rendered = toml::to_string(config)
+        .into_diagnostic()
+        .wrap_err("An error occurred while serializing the configuration.")?;
+
+    let mut output = String::new();
+    let mut section = None::<String>;
+
+    for line in rendered.lines() {
+        if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = Some(header.to_string());
+        }
+
+        let key = line.split_once(" = ").map(|(key, _)| match &section {
+            Some(section) => format!("{section}.{key}"),
+            None => key.to_string(),
+        });
+
+        match key.and_then(|key| provenance(figment, &key)) {
+            Some(provenance) => writeln!(output, "{line} # {provenance}"),
+            None => writeln!(output, "{line}"),
+        }
+        .expect("writing to a string cannot fail");
+    }
+
+    Ok(output)
+}
+
+/// Describes where the value for `key` came from.
+fn provenance(figment: &Figment, key: &str) -> Option<String> {
+    let metadata = figment.find_metadata(key)?;
+
+    Some(match &metadata.source {
+        Some(Source::File(path)) => path.display().to_string(),
+        Some(Source::Code(_)) => "default".to_string(),
+        _ => metadata.name.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use figment::providers::{Format, Serialized, Toml};
+
+    use super::*;
+
+    #[test]
+    fn test_show() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(".change-scribe.toml");
+        fs::write(&file, "[type]\nmin-length = 2\n").unwrap();
+
+        let figment = Figment::new()
+            .merge(Serialized::defaults(Conf::default()))
+            .merge(Toml::file(&file));
+        let config = figment.extract::<Conf>().unwrap();
+
+        let output = show(&config, &figment).unwrap();
+
+        assert!(output.contains(&format!("min-length = 2 # {}\n", file.display())));
+        assert!(output.contains("max-length = 4294967295 # default\n"));
+        assert!(output.contains("[scope]\n"));
+    }
+}
@@ -0,0 +1,670 @@
+use std::{fs, path::Path};
+
+use miette::{Context, Diagnostic, IntoDiagnostic, NamedSource, SourceSpan};
+use regex::Regex;
+use serde::{
+    de::{DeserializeSeed, IgnoredAny, MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use thiserror::Error;
+
+use crate::linting::{is_pattern, path_glob, Cases, Conf, Pattern, Presence, Scopes};
+
+#[derive(Debug, Diagnostic, Error)]
+#[error("Invalid configuration")]
+pub(crate) struct ConfigError {
+    #[source_code]
+    input: NamedSource<String>,
+    #[label("{label}")]
+    span: SourceSpan,
+    label: String,
+    #[help]
+    help: Option<&'static str>,
+}
+
+#[derive(Debug, Diagnostic, Error)]
+#[error("The configuration contradicts itself")]
+#[diagnostic(help("With these settings, every commit would be rejected"))]
+pub(crate) struct ContradictionError {
+    #[related]
+    contradictions: Vec<Contradiction>,
+}
+
+#[derive(Debug, Diagnostic, Error)]
+#[error("The configuration contains invalid patterns")]
+#[diagnostic(help(
+    "Entries starting with `^` are regular expressions, entries containing `*` or `?` are globs"
+))]
+pub(crate) struct PatternError {
+    #[related]
+    patterns: Vec<InvalidPattern>,
+}
+
+#[derive(Debug, Diagnostic, Error, PartialEq)]
+#[error("`{key}` contains `{value}`, which is not a valid pattern: {error}")]
+struct InvalidPattern {
+    key: &'static str,
+    value: String,
+    error: String,
+}
+
+#[derive(Debug, Diagnostic, Error, PartialEq)]
+enum Contradiction {
+    #[error(
+        "`{section}.min-length` ({min}) and `{section}.max-length` ({max}) leave no valid length"
+    )]
+    LengthRange {
+        section: &'static str,
+        min: usize,
+        max: usize,
+    },
+    #[error("`{section}.enum` contains `{value}`, which is not in {case}")]
+    EnumCase {
+        section: &'static str,
+        value: String,
+        case: String,
+    },
+    #[error("`type.enum` is empty, so no type is valid")]
+    TypeEnumEmpty,
+    #[error("`scope.required` is enabled, but `scope.enum` is empty")]
+    ScopeRequiredEnumEmpty,
+    #[error("`header.{affix}.presence` is `required`, but `header.{affix}.pattern` is not set")]
+    AffixRequiredWithoutPattern { affix: &'static str },
+    #[error("For commits of type `{commit_type}`: {contradiction}")]
+    InType {
+        commit_type: String,
+        contradiction: Box<Contradiction>,
+    },
+}
+
+/// Checks that the patterns of the configuration are valid, and that its rules do not contradict
+/// each other.
+pub(crate) fn validate_conf(config: &Conf) -> miette::Result<()> {
+    let patterns = invalid_patterns(config);
+
+    if !patterns.is_empty() {
+        return Err(PatternError { patterns }.into());
+    }
+
+    let contradictions = contradictions(config);
+
+    if contradictions.is_empty() {
+        Ok(())
+    } else {
+        Err(ContradictionError { contradictions }.into())
+    }
+}
+
+fn invalid_patterns(config: &Conf) -> Vec<InvalidPattern> {
+    let mut invalid = Vec::new();
+
+    let configs = std::iter::once(config.clone()).chain(
+        config
+            .types
+            .keys()
+            .map(|commit_type| config.for_type(commit_type)),
+    );
+
+    for config in configs {
+        let keys = [
+            (
+                "type.enum",
+                config.commit_type.types.iter().collect::<Vec<_>>(),
+            ),
+            ("scope.enum", config.commit_scope.scopes.values()),
+            (
+                "breaking.forbidden-branches",
+                config.commit_breaking.forbidden_branches.iter().collect(),
+            ),
+            (
+                "scope.tree",
+                config
+                    .commit_scope
+                    .tree
+                    .iter()
+                    .flat_map(|(key, child)| std::iter::once(key).chain(child.values()))
+                    .collect(),
+            ),
+        ];
+
+        let globs = config.commit_scope.paths.values().flatten().map(|glob| {
+            (
+                "scope.paths",
+                glob,
+                path_glob(glob).err().map(|e| e.kind().to_string()),
+            )
+        });
+
+        let results = keys
+            .into_iter()
+            .flat_map(|(key, values)| {
+                values
+                    .into_iter()
+                    .map(move |value| (key, value, Pattern::parse(value).err()))
+            })
+            .chain(globs);
+
+        for (key, value, error) in results {
+            let Some(error) = error else {
+                continue;
+            };
+
+            let pattern = InvalidPattern {
+                key,
+                value: value.clone(),
+                error,
+            };
+
+            if !invalid.contains(&pattern) {
+                invalid.push(pattern);
+            }
+        }
+    }
+
+    let affixes = [
+        ("header.prefix.pattern", &config.commit_header.prefix),
+        ("header.suffix.pattern", &config.commit_header.suffix),
+    ];
+
+    for (key, affix) in affixes {
+        if let Some(value) = &affix.pattern {
+            if let Err(error) = Regex::new(value) {
+                invalid.push(InvalidPattern {
+                    key,
+                    value: value.clone(),
+                    error: error.to_string(),
+                });
+            }
+        }
+    }
+
+    invalid
+}
+
+fn contradictions(config: &Conf) -> Vec<Contradiction> {
+    let mut contradictions = rule_contradictions(config);
+
+    let affixes = [
+        ("prefix", &config.commit_header.prefix),
+        ("suffix", &config.commit_header.suffix),
+    ];
+
+    for (affix, conf) in affixes {
+        if conf.presence == Presence::Required && conf.pattern.is_none() {
+            contradictions.push(Contradiction::AffixRequiredWithoutPattern { affix });
+        }
+    }
+
+    for commit_type in config.types.keys() {
+        let in_type = rule_contradictions(&config.for_type(commit_type))
+            .into_iter()
+            .filter(|contradiction| !contradictions.contains(contradiction))
+            .map(|contradiction| Contradiction::InType {
+                commit_type: commit_type.clone(),
+                contradiction: Box::new(contradiction),
+            })
+            .collect::<Vec<_>>();
+
+        contradictions.extend(in_type);
+    }
+
+    contradictions
+}
+
+fn rule_contradictions(config: &Conf) -> Vec<Contradiction> {
+    let mut contradictions = Vec::new();
+
+    let sections = [
+        (
+            "type",
+            config.commit_type.min_length,
+            config.commit_type.max_length,
+            config.commit_type.types.iter().collect(),
+            &config.commit_type.case,
+            None,
+        ),
+        (
+            "scope",
+            config.commit_scope.min_length,
+            config.commit_scope.max_length,
+            config.commit_scope.scopes.values(),
+            &config.commit_scope.case,
+            config.commit_scope.separator.as_deref(),
+        ),
+    ];
+
+    for (section, min, max, values, case, separator) in sections {
+        // Both limits are exclusive
+        if min.saturating_add(1) >= max {
+            contradictions.push(Contradiction::LengthRange { section, min, max });
+        }
+
+        contradictions.extend(enum_case_contradictions(section, values, case, separator));
+    }
+
+    if config.commit_type.types.is_empty() {
+        contradictions.push(Contradiction::TypeEnumEmpty);
+    }
+
+    if config.commit_scope.required {
+        match &config.commit_scope.scopes {
+            Scopes::All(scopes) if scopes.is_empty() => {
+                contradictions.push(Contradiction::ScopeRequiredEnumEmpty);
+            }
+            Scopes::All(_) => {}
+            Scopes::ByType(by_type) => {
+                contradictions.extend(by_type.iter().filter(|(_, scopes)| scopes.is_empty()).map(
+                    |(commit_type, _)| Contradiction::InType {
+                        commit_type: commit_type.clone(),
+                        contradiction: Box::new(Contradiction::ScopeRequiredEnumEmpty),
+                    },
+                ))
+            }
+        }
+    }
+
+    contradictions
+}
+
+fn enum_case_contradictions(
+    section: &'static str,
+    values: Vec<&String>,
+    case: &Cases,
+    separator: Option<&str>,
+) -> Vec<Contradiction> {
+    let matches = |value: &str| match separator {
+        Some(separator) if !separator.is_empty() => {
+            value.split(separator).all(|segment| case.matches(segment))
+        }
+        _ => case.matches(value),
+    };
+
+    values
+        .into_iter()
+        .filter(|value| !is_pattern(value) && !matches(value))
+        .map(|value| Contradiction::EnumCase {
+            section,
+            value: value.clone(),
+            case: case.to_string(),
+        })
+        .collect()
+}
+
+/// Reads the configuration at the key path in a TOML or JSON file, checking that it only contains
+/// known keys with valid values. Returns `None` when the file has no configuration at the key path.
+pub(crate) fn read_file(path: &Path, key: &[&str]) -> miette::Result<Option<Conf>> {
+    let input = fs::read_to_string(path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to read `{}`", path.display()))?;
+
+    let json = path
+        .extension()
+        .is_some_and(|extension| extension == "json");
+
+    parse_file(&path.display().to_string(), input, key, json).map_err(|error| (*error).into())
+}
+
+fn parse_file(
+    name: &str,
+    input: String,
+    key: &[&str],
+    json: bool,
+) -> Result<Option<Conf>, Box<ConfigError>> {
+    let (label, span) = if json {
+        let error = match AtKey(key).deserialize(&mut serde_json::Deserializer::from_str(&input)) {
+            Ok(config) => return Ok(config),
+            Err(error) => error,
+        };
+
+        let message = error.to_string();
+        let label = match message.rsplit_once(" at line ") {
+            Some((label, _)) => label.to_string(),
+            None => message,
+        };
+
+        let offset = input
+            .split_inclusive('\n')
+            .take(error.line().saturating_sub(1))
+            .map(str::len)
+            .sum::<usize>()
+            + error.column().saturating_sub(1);
+
+        (label, (offset.min(input.len()), 0))
+    } else {
+        let error = match AtKey(key).deserialize(toml::Deserializer::new(&input)) {
+            Ok(config) => return Ok(config),
+            Err(error) => error,
+        };
+
+        let span = error
+            .span()
+            .map(|span| (span.start, span.end - span.start))
+            .unwrap_or((0, 0));
+
+        (error.message().to_string(), span)
+    };
+
+    Err(Box::new(ConfigError {
+        label,
+        help: Some("Run `change-scribe config dump` to see all keys and their defaults"),
+        input: NamedSource::new(name, input),
+        span: span.into(),
+    }))
+}
+
+/// Deserializes the configuration at a key path, ignoring everything else in the document.
+struct AtKey<'a>(&'a [&'a str]);
+
+impl<'de> DeserializeSeed<'de> for AtKey<'_> {
+    type Value = Option<Conf>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        if self.0.is_empty() {
+            Conf::deserialize(deserializer).map(Some)
+        } else {
+            deserializer.deserialize_map(self)
+        }
+    }
+}
+
+impl<'de> Visitor<'de> for AtKey<'_> {
+    type Value = Option<Conf>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "a table containing `{}`", self.0.join("."))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut config = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            if key == self.0[0] {
+                config = map.next_value_seed(AtKey(&self.0[1..]))?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn validate(input: &str) -> Option<Box<ConfigError>> {
+        parse_file("change-scribe.toml", input.to_string(), &[], false).err()
+    }
+
+    #[test]
+    fn test_default_has_no_contradictions() {
+        assert!(contradictions(&Conf::default()).is_empty());
+    }
+
+    #[test]
+    fn test_length_range() {
+        let mut config = Conf::default();
+        config.commit_type.min_length = 10;
+        config.commit_type.max_length = 5;
+
+        assert_eq!(
+            contradictions(&config),
+            vec![Contradiction::LengthRange {
+                section: "type",
+                min: 10,
+                max: 5
+            }]
+        );
+
+        config.commit_type.min_length = 4;
+        config.commit_type.max_length = 5;
+        assert_eq!(contradictions(&config).len(), 1);
+
+        config.commit_type.max_length = 6;
+        assert!(contradictions(&config).is_empty());
+    }
+
+    #[test]
+    fn test_enum_case() {
+        let mut config = Conf::default();
+        config.commit_scope.scopes = vec!["core".to_string(), "user_settings".to_string()].into();
+
+        assert_eq!(
+            contradictions(&config),
+            vec![Contradiction::EnumCase {
+                section: "scope",
+                value: "user_settings".to_string(),
+                case: "`kebab-case`".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_enum_case_ignores_patterns() {
+        let mut config = Conf::default();
+        config.commit_scope.scopes = vec!["deps_*".to_string(), "^[A-Z]+$".to_string()].into();
+
+        assert!(contradictions(&config).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let mut config = Conf::default();
+        config.commit_type.types = vec!["feat".to_string(), "^feat(".to_string()].into();
+
+        let patterns = invalid_patterns(&config);
+
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].value, "^feat(");
+        assert!(validate_conf(&config).is_err());
+    }
+
+    #[test]
+    fn test_invalid_path_glob() {
+        let mut config = Conf::default();
+        config.commit_scope.paths =
+            BTreeMap::from([("ui".to_string(), vec!["src/ui/[".to_string()])]);
+
+        let patterns = invalid_patterns(&config);
+
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].key, "scope.paths");
+        assert_eq!(patterns[0].value, "src/ui/[");
+    }
+
+    #[test]
+    fn test_invalid_header_pattern() {
+        let mut config = Conf::default();
+        config.commit_header.prefix.pattern = Some("(PROJ-[0-9]+".to_string());
+
+        let patterns = invalid_patterns(&config);
+
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].key, "header.prefix.pattern");
+    }
+
+    #[test]
+    fn test_affix_required_without_pattern() {
+        let mut config = Conf::default();
+        config.commit_header.suffix.presence = Presence::Required;
+
+        assert_eq!(
+            contradictions(&config),
+            vec![Contradiction::AffixRequiredWithoutPattern { affix: "suffix" }]
+        );
+    }
+
+    #[test]
+    fn test_enum_case_per_segment() {
+        let mut config = Conf::default();
+        config.commit_scope.separator = Some("/".to_string());
+        config.commit_scope.scopes = vec!["api/auth".to_string()].into();
+
+        assert!(contradictions(&config).is_empty());
+    }
+
+    #[test]
+    fn test_type_enum_empty() {
+        let mut config = Conf::default();
+        config.commit_type.types = vec![].into();
+
+        assert_eq!(contradictions(&config), vec![Contradiction::TypeEnumEmpty]);
+    }
+
+    #[test]
+    fn test_scope_required_enum_empty() {
+        let mut config = Conf::default();
+        config.commit_scope.required = true;
+        config.commit_scope.scopes = vec![].into();
+
+        assert_eq!(
+            contradictions(&config),
+            vec![Contradiction::ScopeRequiredEnumEmpty]
+        );
+    }
+
+    #[test]
+    fn test_scope_required_enum_by_type_empty() {
+        let mut config = Conf::default();
+        config.commit_scope.required = true;
+        config.commit_scope.scopes = Scopes::ByType(
+            [
+                ("ci".to_string(), vec![].into()),
+                ("feat".to_string(), vec!["ui".to_string()].into()),
+            ]
+            .into(),
+        );
+
+        assert_eq!(
+            contradictions(&config),
+            vec![Contradiction::InType {
+                commit_type: "ci".to_string(),
+                contradiction: Box::new(Contradiction::ScopeRequiredEnumEmpty),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_type_overrides() {
+        let config =
+            toml::from_str::<Conf>("[scope]\nenum = []\n\n[types.feat]\nscope.required = true\n")
+                .unwrap();
+
+        assert_eq!(
+            contradictions(&config),
+            vec![Contradiction::InType {
+                commit_type: "feat".to_string(),
+                contradiction: Box::new(Contradiction::ScopeRequiredEnumEmpty),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_nested_key() {
+        let config = parse_file(
+            "Cargo.toml",
+            "[package]\nname = \"crate\"\n\n[package.metadata.change-scribe]\nscope.required = true\n"
+                .to_string(),
+            &["package", "metadata", "change-scribe"],
+            false,
+        )
+        .unwrap()
+        .unwrap();
+
+        assert!(config.commit_scope.required);
+    }
+
+    #[test]
+    fn test_nested_key_missing() {
+        let config = parse_file(
+            "Cargo.toml",
+            "[package]\nname = \"crate\"\n".to_string(),
+            &["package", "metadata", "change-scribe"],
+            false,
+        )
+        .unwrap();
+
+        assert!(config.is_none());
+    }
+
+    #[test]
+    fn test_nested_key_invalid() {
+        let error = parse_file(
+            "pyproject.toml",
+            "[tool.change-scribe.type]\nemum = []\n".to_string(),
+            &["tool", "change-scribe"],
+            false,
+        )
+        .unwrap_err();
+
+        assert!(error.label.contains("unknown field `emum`"));
+        assert_eq!(error.span, (26, 4).into());
+    }
+
+    #[test]
+    fn test_json() {
+        let config = parse_file(
+            "package.json",
+            "{\"name\": \"package\", \"change-scribe\": {\"scope\": {\"required\": true}}}"
+                .to_string(),
+            &["change-scribe"],
+            true,
+        )
+        .unwrap()
+        .unwrap();
+
+        assert!(config.commit_scope.required);
+    }
+
+    #[test]
+    fn test_json_invalid() {
+        let error = parse_file(
+            "package.json",
+            "{\n  \"change-scribe\": {\"scope\": {\"case\": \"kebabcase\"}}\n}".to_string(),
+            &["change-scribe"],
+            true,
+        )
+        .unwrap_err();
+
+        assert!(error.label.contains("unknown variant `kebabcase`"));
+        assert_eq!(error.span, (50, 0).into());
+    }
+
+    #[test]
+    fn test_valid() {
+        assert!(validate("[type]\nenum = [\"feat\"]\ncase = \"snake\"").is_none());
+    }
+
+    #[test]
+    fn test_unknown_key() {
+        let error = validate("[type]\nemum = [\"feat\"]").unwrap();
+
+        assert!(error.label.contains("unknown field `emum`"));
+        assert_eq!(error.span, (7, 4).into());
+    }
+
+    #[test]
+    fn test_unknown_section() {
+        let error = validate("[typ]\nenum = [\"feat\"]").unwrap();
+
+        assert!(error.label.contains("unknown field `typ`"));
+    }
+
+    #[test]
+    fn test_invalid_casing() {
+        let error = validate("[scope]\ncase = \"kebabcase\"").unwrap();
+
+        assert!(error.label.contains("`camel`, `kebab`, `pascal`, `snake`"));
+        assert_eq!(error.span, (15, 11).into());
+    }
+
+    #[test]
+    fn test_invalid_type() {
+        let error = validate("[scope]\nrequired = \"yes\"").unwrap();
+
+        assert!(error.label.contains("expected a boolean"));
+    }
+}
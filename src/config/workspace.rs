@@ -0,0 +1,185 @@
+use std::{fs, path::Path};
+
+use crate::linting::{Conf, ScopeSource};
+use miette::{Context, IntoDiagnostic};
+
+/// Replaces the `*` wildcard in the scope enums with the scopes read from `scope.from`, found from
+/// `start`.
+pub(super) fn expand_scopes(config: &mut Conf, start: &Path) -> miette::Result<()> {
+    let Some(ScopeSource::CargoWorkspace) = config.commit_scope.from else {
+        return Ok(());
+    };
+
+    let members = cargo_workspace_members(start)?;
+
+    config.commit_scope.scopes.expand_wildcard(&members);
+
+    for overrides in config.types.values_mut() {
+        overrides.map_scopes(|scopes| scopes.expand_wildcard(&members));
+    }
+
+    Ok(())
+}
+
+/// Package names of the members of the nearest Cargo workspace containing `start`, including the
+/// root package if there is one.
+fn cargo_workspace_members(start: &Path) -> miette::Result<Vec<String>> {
+    let mut workspace = None;
+
+    for dir in start.ancestors() {
+        let path = dir.join("Cargo.toml");
+
+        if path.is_file() {
+            let manifest = read_manifest(&path)?;
+
+            if manifest.contains_key("workspace") {
+                workspace = Some((dir, manifest));
+                break;
+            }
+        }
+    }
+
+    let Some((root, manifest)) = workspace else {
+        miette::bail!(
+            help = "`scope.from = \"cargo-workspace\"` needs a `Cargo.toml` with a `[workspace]` table",
+            "No Cargo workspace found"
+        );
+    };
+
+    let mut names = package_name(&manifest).into_iter().collect::<Vec<_>>();
+
+    let exclude = strings(&manifest, "exclude")
+        .iter()
+        .map(|path| root.join(path))
+        .collect::<Vec<_>>();
+
+    for member in strings(&manifest, "members") {
+        let pattern = root.join(&member);
+
+        let paths = glob::glob(&pattern.to_string_lossy())
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Invalid workspace member `{member}`"))?;
+
+        for path in paths.filter_map(Result::ok) {
+            let path = path.join("Cargo.toml");
+
+            if !path.is_file() || exclude.iter().any(|exclude| path.starts_with(exclude)) {
+                continue;
+            }
+
+            names.extend(package_name(&read_manifest(&path)?));
+        }
+    }
+
+    names.sort();
+    names.dedup();
+
+    Ok(names)
+}
+
+fn read_manifest(path: &Path) -> miette::Result<toml::Table> {
+    fs::read_to_string(path)
+        .into_diagnostic()
+        .and_then(|input| input.parse::<toml::Table>().into_diagnostic())
+        .wrap_err_with(|| format!("Failed to read `{}`", path.display()))
+}
+
+fn package_name(manifest: &toml::Table) -> Option<String> {
+    manifest
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(str::to_string)
+}
+
+fn strings(manifest: &toml::Table, key: &str) -> Vec<String> {
+    manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get(key))
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_str)
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, input: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, input).unwrap();
+    }
+
+    #[test]
+    fn test_cargo_workspace_members() {
+        let root = tempfile::tempdir().unwrap();
+        write(
+            &root.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\n\n[workspace]\nmembers = [\"crates/*\", \"tools/xtask\"]\nexclude = [\"crates/old\"]\n",
+        );
+        write(
+            &root.path().join("crates/core/Cargo.toml"),
+            "[package]\nname = \"app-core\"\n",
+        );
+        write(
+            &root.path().join("crates/cli/Cargo.toml"),
+            "[package]\nname = \"app-cli\"\n",
+        );
+        write(
+            &root.path().join("crates/old/Cargo.toml"),
+            "[package]\nname = \"app-old\"\n",
+        );
+        write(
+            &root.path().join("tools/xtask/Cargo.toml"),
+            "[package]\nname = \"xtask\"\n",
+        );
+
+        assert_eq!(
+            cargo_workspace_members(&root.path().join("crates/core")).unwrap(),
+            vec!["app", "app-cli", "app-core", "xtask"]
+        );
+    }
+
+    #[test]
+    fn test_no_workspace() {
+        let root = tempfile::tempdir().unwrap();
+        write(
+            &root.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\n",
+        );
+
+        assert!(cargo_workspace_members(root.path()).is_err());
+    }
+
+    #[test]
+    fn test_expand_scopes() {
+        let root = tempfile::tempdir().unwrap();
+        write(
+            &root.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"core\"]\n",
+        );
+        write(
+            &root.path().join("core/Cargo.toml"),
+            "[package]\nname = \"core\"\n",
+        );
+
+        let mut config: Conf = toml::from_str(
+            "[scope]\nfrom = \"cargo-workspace\"\nenum = [\"*\", \"deps\"]\n\n[types.ci.scope]\nenum = [\"*\", \"github\"]\n",
+        )
+        .unwrap();
+
+        expand_scopes(&mut config, root.path()).unwrap();
+
+        assert_eq!(
+            config.commit_scope.scopes,
+            vec!["core".to_string(), "deps".to_string()].into()
+        );
+        assert_eq!(
+            config.for_type("ci").commit_scope.scopes,
+            vec!["core".to_string(), "github".to_string()].into()
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use std::process::Command;
+
+use miette::{Context, IntoDiagnostic};
+
+/// Paths of the files in the index that differ from `HEAD`.
+pub(crate) fn staged_files() -> miette::Result<Vec<String>> {
+    Ok(lines(&git(&["diff", "--cached", "--name-only"])?))
+}
+
+/// Paths of the files changed by a commit.
+pub(crate) fn changed_files(commit: &str) -> miette::Result<Vec<String>> {
+    Ok(lines(&git(&[
+        "diff-tree",
+        "--no-commit-id",
+        "--name-only",
+        "-r",
+        "--root",
+        commit,
+    ])?))
+}
+
+/// Hashes and messages of the commits in a revision range, e.g. `main..HEAD`, oldest first. Merge
+/// commits are skipped, as their messages are generated by git.
+pub(crate) fn commits(range: &str) -> miette::Result<Vec<(String, String)>> {
+    let output = git(&[
+        "log",
+        "--reverse",
+        "--no-merges",
+        "--format=%H%x00%B%x00",
+        range,
+    ])?;
+
+    let mut fields = output.split('\0').map(str::trim);
+    let mut commits = Vec::new();
+
+    while let (Some(hash), Some(message)) = (fields.next(), fields.next()) {
+        commits.push((hash.to_string(), message.to_string()));
+    }
+
+    Ok(commits)
+}
+
+/// Name of the branch a revision refers to, e.g. `HEAD` for the checked out branch. `None` when
+/// the revision is not a branch, like a detached `HEAD`, or there is no repository.
+pub(crate) fn branch(revision: &str) -> Option<String> {
+    git(&["rev-parse", "--symbolic-full-name", revision])
+        .ok()?
+        .trim()
+        .strip_prefix("refs/heads/")
+        .map(str::to_string)
+}
+
+/// Name of the branch the commits of a revision range are on, taken from the end of the range,
+/// e.g. `feature` for `main..feature`. An open range like `main..` ends at `HEAD`.
+pub(crate) fn range_branch(range: &str) -> Option<String> {
+    match range.rsplit_once("..") {
+        Some((_, "")) => branch("HEAD"),
+        Some((_, end)) => branch(end),
+        None => branch(range),
+    }
+}
+
+fn git(args: &[&str]) -> miette::Result<String> {
+    let command = format!("git {}", args[0]);
+
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to run `{command}`"))?;
+
+    if !output.status.success() {
+        miette::bail!(
+            "`{command}` exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn lines(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
@@ -0,0 +1,61 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::Commit;
+
+use super::Conf;
+
+/// Rules for the commit body.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub(crate) struct BodyConf {
+    /// Whether a commit must have a body.
+    pub required: bool,
+}
+
+pub(super) fn commit_body_required(commit: &Commit, config: &Conf) -> bool {
+    config.commit_body.required && commit.body.is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_commit() -> Commit<'static> {
+        Commit {
+            commit_type: "fix",
+            scope: vec![],
+            breaking_change: false,
+            subject: "subject",
+            prefix: None,
+            suffix: None,
+            body: None,
+            footer: Default::default(),
+            source: "fix subject".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_required() {
+        let mut config = Conf::default();
+        config.commit_body.required = true;
+
+        assert!(commit_body_required(&sample_commit(), &config));
+    }
+
+    #[test]
+    fn test_required_present() {
+        let mut commit = sample_commit();
+        commit.body = Some("body");
+
+        let mut config = Conf::default();
+        config.commit_body.required = true;
+
+        assert!(!commit_body_required(&commit, &config));
+    }
+
+    #[test]
+    fn test_not_required() {
+        assert!(!commit_body_required(&sample_commit(), &Conf::default()));
+    }
+}
@@ -0,0 +1,240 @@
+use miette::{Diagnostic, SourceSpan};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{parsing::BREAKING_CHANGE_TOKENS, Commit};
+
+use super::{Conf, LintError, LintErrorKind, Patterns};
+
+/// Rules for breaking changes, marked by a `!` in the header or a `BREAKING CHANGE` footer.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub(crate) struct BreakingConf {
+    /// Whether breaking changes are allowed. Disable it per type, e.g. in `[types.docs]`.
+    pub allowed: bool,
+    /// Whether a `!` in the header requires a `BREAKING CHANGE` footer describing the change.
+    pub require_footer: bool,
+    /// Minimum length of the `BREAKING CHANGE` description.
+    pub min_description_length: usize,
+    /// Branches on which breaking changes are not allowed, e.g. `release/*`. Like in
+    /// `type.enum`, entries can be globs or regular expressions.
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    pub forbidden_branches: Patterns,
+}
+
+impl Default for BreakingConf {
+    fn default() -> Self {
+        Self {
+            allowed: true,
+            require_footer: false,
+            min_description_length: usize::MIN,
+            forbidden_branches: Patterns::default(),
+        }
+    }
+}
+
+/// The description of the breaking change, if the commit has a `BREAKING CHANGE` or
+/// `BREAKING-CHANGE` footer.
+fn description<'a>(commit: &Commit<'a>) -> Option<&'a str> {
+    BREAKING_CHANGE_TOKENS
+        .iter()
+        .find_map(|token| commit.footer_value(token))
+        .map(|description| description.trim())
+}
+
+pub(super) fn commit_breaking_forbidden(commit: &Commit, config: &Conf) -> bool {
+    commit.breaking_change && !config.commit_breaking.allowed
+}
+
+pub(super) fn commit_breaking_footer_required(commit: &Commit, config: &Conf) -> bool {
+    config.commit_breaking.require_footer
+        && commit.breaking_change
+        && description(commit).is_none_or(str::is_empty)
+}
+
+pub(super) fn commit_breaking_description_too_short(commit: &Commit, config: &Conf) -> bool {
+    description(commit).is_some_and(|description| {
+        config.length_unit.measure(description) <= config.commit_breaking.min_description_length
+    })
+}
+
+#[derive(Debug, Diagnostic, Error)]
+#[error("The branch could not be determined")]
+#[diagnostic(
+    severity(Warning),
+    help("Pass `--branch` to check breaking changes against `breaking.forbidden-branches`")
+)]
+struct UnknownBranch {
+    #[source_code]
+    input: String,
+    #[label("This breaking change is not checked")]
+    span: SourceSpan,
+}
+
+/// Checks a breaking change against the branch it is committed on, using
+/// `breaking.forbidden-branches`. Without a branch, e.g. on a detached `HEAD`, a warning is
+/// reported instead.
+pub(super) fn check_branch(
+    commit: &Commit,
+    config: &Conf,
+    branch: Option<&str>,
+) -> Option<miette::Report> {
+    if !commit.breaking_change || config.commit_breaking.forbidden_branches.is_empty() {
+        return None;
+    }
+
+    let Some(branch) = branch else {
+        return Some(
+            UnknownBranch {
+                input: commit.source.clone(),
+                span: commit.breaking_span().into(),
+            }
+            .into(),
+        );
+    };
+
+    if !config.commit_breaking.forbidden_branches.matches(branch) {
+        return None;
+    }
+
+    Some(
+        LintError {
+            input: commit.source.clone(),
+            span: commit.breaking_span().into(),
+            label: Some("This marks a breaking change"),
+            help: Some(format!("Breaking changes are not allowed on `{branch}`").leak()),
+            kind: LintErrorKind::BreakingBranchForbidden,
+        }
+        .into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::parse;
+
+    fn sample_commit() -> Commit<'static> {
+        Commit {
+            commit_type: "feat",
+            scope: vec![],
+            breaking_change: true,
+            subject: "subject",
+            prefix: None,
+            suffix: None,
+            body: None,
+            footer: Default::default(),
+            source: "feat!: subject".to_string(),
+        }
+    }
+
+    fn with_description(description: &'static str) -> Commit<'static> {
+        parse(format!("feat!: subject\n\nBREAKING CHANGE: {description}").leak()).unwrap()
+    }
+
+    #[test]
+    fn test_forbidden() {
+        let mut config = Conf::default();
+        assert!(!commit_breaking_forbidden(&sample_commit(), &config));
+
+        config.commit_breaking.allowed = false;
+        assert!(commit_breaking_forbidden(&sample_commit(), &config));
+
+        let mut commit = sample_commit();
+        commit.breaking_change = false;
+        assert!(!commit_breaking_forbidden(&commit, &config));
+    }
+
+    #[test]
+    fn test_forbidden_per_type() {
+        let config: Conf = toml::from_str("[types.docs]\nbreaking.allowed = false\n").unwrap();
+
+        let mut commit = sample_commit();
+        assert!(!commit_breaking_forbidden(
+            &commit,
+            &config.for_type(commit.commit_type)
+        ));
+
+        commit.commit_type = "docs";
+        assert!(commit_breaking_forbidden(
+            &commit,
+            &config.for_type(commit.commit_type)
+        ));
+    }
+
+    #[test]
+    fn test_footer_required() {
+        let mut config = Conf::default();
+        assert!(!commit_breaking_footer_required(&sample_commit(), &config));
+
+        config.commit_breaking.require_footer = true;
+        assert!(commit_breaking_footer_required(&sample_commit(), &config));
+        assert!(commit_breaking_footer_required(
+            &with_description(" "),
+            &config
+        ));
+        assert!(!commit_breaking_footer_required(
+            &with_description("the API changed"),
+            &config
+        ));
+    }
+
+    #[test]
+    fn test_footer_required_synonym() {
+        let commit = parse("feat!: subject\n\nBREAKING-CHANGE: the API changed").unwrap();
+
+        let mut config = Conf::default();
+        config.commit_breaking.require_footer = true;
+
+        assert!(!commit_breaking_footer_required(&commit, &config));
+    }
+
+    #[test]
+    fn test_description_too_short() {
+        let mut config = Conf::default();
+        config.commit_breaking.min_description_length = 10;
+
+        assert!(commit_breaking_description_too_short(
+            &with_description("changed"),
+            &config
+        ));
+        assert!(commit_breaking_description_too_short(
+            &with_description("API change"),
+            &config
+        ));
+        assert!(!commit_breaking_description_too_short(
+            &with_description("the API changed"),
+            &config
+        ));
+        assert!(!commit_breaking_description_too_short(
+            &sample_commit(),
+            &config
+        ));
+    }
+
+    #[test]
+    fn test_branch() {
+        let mut config = Conf::default();
+        config.commit_breaking.forbidden_branches =
+            vec!["main".to_string(), "release/*".to_string()].into();
+
+        assert!(check_branch(&sample_commit(), &config, Some("release/1.x")).is_some());
+        assert!(check_branch(&sample_commit(), &config, Some("main")).is_some());
+        assert!(check_branch(&sample_commit(), &config, Some("feature/api")).is_none());
+
+        let mut commit = sample_commit();
+        commit.breaking_change = false;
+        assert!(check_branch(&commit, &config, Some("main")).is_none());
+    }
+
+    #[test]
+    fn test_unknown_branch() {
+        let mut config = Conf::default();
+        assert!(check_branch(&sample_commit(), &config, None).is_none());
+
+        config.commit_breaking.forbidden_branches = vec!["main".to_string()].into();
+        let report = check_branch(&sample_commit(), &config, None).unwrap();
+        assert_eq!(report.severity(), Some(miette::Severity::Warning));
+    }
+}
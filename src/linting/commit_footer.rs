@@ -0,0 +1,230 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    parsing::{is_breaking_change_token, BREAKING_CHANGE_TOKENS, DEFAULT_SEPARATORS},
+    Commit,
+};
+
+use super::{Cases, Casing, Conf};
+
+/// Rules for the commit footers.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub(crate) struct FooterConf {
+    /// Footers every commit must have, e.g. `Refs`.
+    pub required: Vec<String>,
+    /// Case of the footer keys, or a list of accepted cases. `BREAKING CHANGE` is always allowed.
+    pub key_case: Cases,
+    /// Spelling of the breaking change token. Both `BREAKING CHANGE` and `BREAKING-CHANGE` are
+    /// accepted when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub breaking_token: Option<BreakingToken>,
+    /// Separators between the key and the value of a footer, like git's `trailer.separators`.
+    pub separators: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub(crate) enum BreakingToken {
+    #[serde(rename = "BREAKING CHANGE")]
+    Space,
+    #[serde(rename = "BREAKING-CHANGE")]
+    Hyphen,
+}
+
+impl BreakingToken {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            BreakingToken::Space => "BREAKING CHANGE",
+            BreakingToken::Hyphen => "BREAKING-CHANGE",
+        }
+    }
+}
+
+impl Default for FooterConf {
+    fn default() -> Self {
+        Self {
+            required: Vec::new(),
+            key_case: Casing::Any.into(),
+            breaking_token: None,
+            separators: DEFAULT_SEPARATORS.map(str::to_string).to_vec(),
+        }
+    }
+}
+
+pub(super) fn commit_footer_required(commit: &Commit, config: &Conf) -> bool {
+    !missing_footers(commit, config).is_empty()
+}
+
+pub(super) fn missing_footers<'a>(commit: &Commit, config: &'a Conf) -> Vec<&'a str> {
+    config
+        .commit_footer
+        .required
+        .iter()
+        .filter(|key| commit.footer_value(key).is_none())
+        .map(String::as_str)
+        .collect()
+}
+
+pub(super) fn commit_footer_key_case_invalid(commit: &Commit, config: &Conf) -> bool {
+    invalid_footer_key(commit, config).is_some()
+}
+
+/// Finds the first footer key that is not in `footer.key-case`, and returns its span.
+pub(super) fn invalid_footer_key(commit: &Commit, config: &Conf) -> Option<(usize, usize)> {
+    commit
+        .footer
+        .iter()
+        .find(|footer| {
+            !is_breaking_change_token(footer.key)
+                && !config.commit_footer.key_case.matches(footer.key)
+        })
+        .map(|footer| (footer.offset, footer.key.len()))
+}
+
+/// The correct spelling of a breaking change token, if the key is a misspelled one.
+fn token_spelling(key: &str, config: &Conf) -> Option<&'static str> {
+    let token = BREAKING_CHANGE_TOKENS
+        .into_iter()
+        .find(|token| key.eq_ignore_ascii_case(token))?;
+
+    let spelling = config
+        .commit_footer
+        .breaking_token
+        .map_or(token, |token| token.as_str());
+
+    (spelling != key).then_some(spelling)
+}
+
+pub(super) fn commit_footer_token_invalid(commit: &Commit, config: &Conf) -> bool {
+    invalid_footer_token(commit, config).is_some()
+}
+
+/// Finds the first misspelled breaking change token, and returns its span and correct spelling.
+pub(super) fn invalid_footer_token(
+    commit: &Commit,
+    config: &Conf,
+) -> Option<((usize, usize), &'static str)> {
+    commit.footer.iter().find_map(|footer| {
+        let spelling = token_spelling(footer.key, config)?;
+
+        Some(((footer.offset, footer.key.len()), spelling))
+    })
+}
+
+pub(super) fn fix_commit_footer_token(commit: &mut Commit<'static>, config: &Conf) {
+    for footer in &mut commit.footer {
+        if let Some(spelling) = token_spelling(footer.key, config) {
+            footer.key = spelling;
+            commit.breaking_change = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::parse;
+
+    fn sample_commit() -> Commit<'static> {
+        Commit {
+            commit_type: "fix",
+            scope: vec![],
+            breaking_change: false,
+            subject: "subject",
+            prefix: None,
+            suffix: None,
+            body: None,
+            footer: Default::default(),
+            source: "fix subject".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_key_case() {
+        let commit =
+            parse("fix: subject\n\nBREAKING CHANGE: yes\nReviewed-by: Z\nrefs: #123").unwrap();
+
+        assert!(!commit_footer_key_case_invalid(&commit, &Conf::default()));
+
+        let mut config = Conf::default();
+        config.commit_footer.key_case = Cases::Many(vec![Casing::Start, Casing::Upper]);
+
+        assert_eq!(invalid_footer_key(&commit, &config), Some((50, 4)));
+    }
+
+    #[test]
+    fn test_key_case_ignores_breaking_tokens() {
+        let commit = parse("fix: subject\n\nBREAKING-CHANGE: yes\nbreaking change: yes").unwrap();
+
+        let mut config = Conf::default();
+        config.commit_footer.key_case = Casing::Kebab.into();
+
+        assert!(!commit_footer_key_case_invalid(&commit, &config));
+    }
+
+    #[test]
+    fn test_token() {
+        let commit = parse("fix: subject\n\nBREAKING CHANGE: yes\nRefs: #1").unwrap();
+
+        assert!(!commit_footer_token_invalid(&commit, &Conf::default()));
+
+        let mut config = Conf::default();
+        config.commit_footer.breaking_token = Some(BreakingToken::Hyphen);
+
+        assert_eq!(
+            invalid_footer_token(&commit, &config),
+            Some(((14, 15), "BREAKING-CHANGE"))
+        );
+    }
+
+    #[test]
+    fn test_token_misspelled() {
+        let commit = parse("fix: subject\n\nBreaking-Change: yes").unwrap();
+
+        assert_eq!(
+            invalid_footer_token(&commit, &Conf::default()),
+            Some(((14, 15), "BREAKING-CHANGE"))
+        );
+    }
+
+    #[test]
+    fn test_fix_token() {
+        let mut commit = parse("fix: subject\n\nbreaking change: yes").unwrap();
+
+        let config = Conf::default();
+        fix_commit_footer_token(&mut commit, &config);
+
+        assert_eq!(commit.footer_value("BREAKING CHANGE"), Some("yes"));
+        assert!(commit.breaking_change);
+        assert!(!commit_footer_token_invalid(&commit, &config));
+    }
+
+    #[test]
+    fn test_required() {
+        let mut config = Conf::default();
+        config.commit_footer.required = vec!["Refs".to_string()];
+
+        assert!(commit_footer_required(&sample_commit(), &config));
+    }
+
+    #[test]
+    fn test_required_present() {
+        let commit = parse("fix: subject\n\nRefs: #123").unwrap();
+
+        let mut config = Conf::default();
+        config.commit_footer.required = vec!["Refs".to_string()];
+
+        assert!(!commit_footer_required(&commit, &config));
+    }
+
+    #[test]
+    fn test_missing_footers() {
+        let commit = parse("fix: subject\n\nRefs: #123").unwrap();
+
+        let mut config = Conf::default();
+        config.commit_footer.required = vec!["Refs".to_string(), "Reviewed-by".to_string()];
+
+        assert_eq!(missing_footers(&commit, &config), vec!["Reviewed-by"]);
+    }
+}
@@ -0,0 +1,140 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{parsing::Grammar, Commit};
+
+use super::Conf;
+
+/// Rules for the text around the conventional header.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub(crate) struct HeaderConf {
+    /// Text before the type, e.g. the ticket in `PROJ-123 feat: subject`.
+    pub prefix: AffixConf,
+    /// Text after the subject, e.g. the pull request in `feat: subject (#123)`.
+    pub suffix: AffixConf,
+}
+
+/// Rules for a prefix or suffix of the header.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub(crate) struct AffixConf {
+    /// Regular expression that matches the prefix or suffix, without the space that separates it
+    /// from the rest of the header. Without a pattern, the header has no prefix or suffix.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Whether the prefix or suffix is `optional`, `required` or `forbidden`.
+    pub presence: Presence,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum Presence {
+    #[default]
+    Optional,
+    Required,
+    Forbidden,
+}
+
+impl Conf {
+    /// The grammar to parse messages with. Invalid patterns are reported when the configuration is
+    /// loaded, and are ignored here.
+    pub(crate) fn grammar(&self) -> Grammar {
+        Grammar::new(
+            self.commit_header.prefix.pattern.as_deref(),
+            self.commit_header.suffix.pattern.as_deref(),
+        )
+        .unwrap_or_default()
+        .with_separators(&self.commit_footer.separators)
+    }
+}
+
+pub(super) fn commit_header_prefix_required(commit: &Commit, config: &Conf) -> bool {
+    config.commit_header.prefix.presence == Presence::Required && commit.prefix.is_none()
+}
+
+pub(super) fn commit_header_prefix_forbidden(commit: &Commit, config: &Conf) -> bool {
+    config.commit_header.prefix.presence == Presence::Forbidden && commit.prefix.is_some()
+}
+
+pub(super) fn commit_header_suffix_required(commit: &Commit, config: &Conf) -> bool {
+    config.commit_header.suffix.presence == Presence::Required && commit.suffix.is_none()
+}
+
+pub(super) fn commit_header_suffix_forbidden(commit: &Commit, config: &Conf) -> bool {
+    config.commit_header.suffix.presence == Presence::Forbidden && commit.suffix.is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::parse_with;
+
+    fn config() -> Conf {
+        let mut config = Conf::default();
+        config.commit_header.prefix.pattern = Some("[A-Z]+-[0-9]+".to_string());
+        config.commit_header.suffix.pattern = Some(r"\(#[0-9]+\)".to_string());
+
+        config
+    }
+
+    fn parse(message: &'static str, config: &Conf) -> Commit<'static> {
+        parse_with(message, &config.grammar()).unwrap()
+    }
+
+    #[test]
+    fn test_prefix_required() {
+        let mut config = config();
+        config.commit_header.prefix.presence = Presence::Required;
+
+        assert!(commit_header_prefix_required(
+            &parse("feat: subject", &config),
+            &config
+        ));
+        assert!(!commit_header_prefix_required(
+            &parse("PROJ-123 feat: subject", &config),
+            &config
+        ));
+    }
+
+    #[test]
+    fn test_prefix_forbidden() {
+        let mut config = config();
+        config.commit_header.prefix.presence = Presence::Forbidden;
+
+        assert!(commit_header_prefix_forbidden(
+            &parse("PROJ-123 feat: subject", &config),
+            &config
+        ));
+        assert!(!commit_header_prefix_forbidden(
+            &parse("feat: subject", &config),
+            &config
+        ));
+    }
+
+    #[test]
+    fn test_suffix_required() {
+        let mut config = config();
+        config.commit_header.suffix.presence = Presence::Required;
+
+        assert!(commit_header_suffix_required(
+            &parse("feat: subject", &config),
+            &config
+        ));
+        assert!(!commit_header_suffix_required(
+            &parse("feat: subject (#12)", &config),
+            &config
+        ));
+    }
+
+    #[test]
+    fn test_suffix_forbidden() {
+        let mut config = config();
+        config.commit_header.suffix.presence = Presence::Forbidden;
+
+        assert!(commit_header_suffix_forbidden(
+            &parse("feat: subject (#12)\n\nbody", &config),
+            &config
+        ));
+    }
+}
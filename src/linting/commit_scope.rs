@@ -1,33 +1,142 @@
-use cruet::Inflector;
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::Commit;
 
-use super::{Casing, Conf};
+use super::{
+    scope_tree::{check_segments, Branches, InvalidSegment},
+    Cases, Conf, Patterns,
+};
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "kebab-case")]
+/// Rules for the commit scope.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
 pub(crate) struct ScopeConf {
+    /// Whether a commit must have a scope.
     pub required: bool,
+    /// Allowed scopes. Entries starting with `^` or containing `\`, `+`, `(`, `)`, `|` or `$` are
+    /// regular expressions, and entries containing `*` or `?` are globs. `*` matches any scope.
     #[serde(rename = "enum")]
-    pub scopes: Vec<String>,
+    pub scopes: Scopes,
+    /// Minimum length of each scope.
     pub min_length: usize,
+    /// Maximum length of each scope.
     pub max_length: usize,
-    pub case: Casing,
+    /// Case of each scope, or of each segment of hierarchical scopes. Can be a list of accepted
+    /// cases.
+    pub case: Cases,
+    /// Separator between the segments of hierarchical scopes, e.g. `/` for `api/auth`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub separator: Option<String>,
+    /// Allowed hierarchical scopes, as a tree of segments. Each segment of a scope must be a child
+    /// of the previous one, e.g. `api = ["auth", "billing"]` allows `api`, `api/auth` and
+    /// `api/billing`.
+    #[serde(skip_serializing_if = "Branches::is_empty")]
+    pub tree: Branches,
+    /// Globs of the files each scope covers, e.g. `ui = ["frontend/**"]`. Used to check the scope
+    /// against the changed files with `lint --staged` and `lint --range`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub paths: BTreeMap<String, Vec<String>>,
+    /// Where to read more allowed scopes from. The `*` wildcard in `enum` is replaced by the
+    /// scopes read from it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<ScopeSource>,
+}
+
+/// A source of allowed scopes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum ScopeSource {
+    /// The package names of the members of the Cargo workspace.
+    CargoWorkspace,
 }
 
 impl Default for ScopeConf {
     fn default() -> Self {
         Self {
             required: false,
-            scopes: vec!["*".to_string()],
+            scopes: Scopes::All(vec!["*".to_string()].into()),
             min_length: usize::MIN,
             max_length: u32::MAX as usize,
-            case: Casing::default(),
+            case: Cases::default(),
+            separator: None,
+            tree: Branches::default(),
+            paths: BTreeMap::new(),
+            from: None,
+        }
+    }
+}
+
+impl ScopeConf {
+    /// The segments of a scope, split on the separator. Without a separator, the scope is a single
+    /// segment.
+    pub(crate) fn segments<'a>(&self, scope: &'a str) -> Vec<&'a str> {
+        match self.separator.as_deref() {
+            Some(separator) if !separator.is_empty() => scope.split(separator).collect(),
+            _ => vec![scope],
+        }
+    }
+}
+
+/// Allowed scopes, either for all commit types or keyed by commit type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub(crate) enum Scopes {
+    /// Scopes allowed for every commit type.
+    All(Patterns),
+    /// Scopes allowed per commit type. The `*` key applies to types without their own entry, and
+    /// types without any entry may use any scope.
+    ByType(BTreeMap<String, Patterns>),
+}
+
+impl Scopes {
+    /// The scopes allowed for a commit type, or `None` when any scope is allowed.
+    pub(crate) fn allowed(&self, commit_type: &str) -> Option<&Patterns> {
+        let scopes = match self {
+            Scopes::All(scopes) => scopes,
+            Scopes::ByType(by_type) => by_type.get(commit_type).or_else(|| by_type.get("*"))?,
+        };
+
+        if scopes.contains(&"*".to_string()) {
+            None
+        } else {
+            Some(scopes)
+        }
+    }
+
+    /// Replaces the `*` wildcard in each list of scopes with `names`.
+    pub(crate) fn expand_wildcard(&mut self, names: &[String]) {
+        let lists = match self {
+            Scopes::All(scopes) => vec![scopes],
+            Scopes::ByType(by_type) => by_type.values_mut().collect(),
+        };
+
+        for scopes in lists {
+            if let Some(index) = scopes.iter().position(|scope| scope == "*") {
+                let mut expanded = scopes.to_vec();
+                expanded.splice(index..=index, names.iter().cloned());
+                *scopes = expanded.into();
+            }
+        }
+    }
+
+    /// All scopes mentioned, regardless of the commit type.
+    pub(crate) fn values(&self) -> Vec<&String> {
+        match self {
+            Scopes::All(scopes) => scopes.iter().collect(),
+            Scopes::ByType(by_type) => by_type.values().flat_map(|scopes| scopes.iter()).collect(),
         }
     }
 }
 
+impl From<Vec<String>> for Scopes {
+    fn from(scopes: Vec<String>) -> Self {
+        Scopes::All(scopes.into())
+    }
+}
+
 pub(super) fn commit_scope_required(commit: &Commit, config: &Conf) -> bool {
     if config.commit_scope.required {
         commit.scope.is_empty()
@@ -37,37 +146,111 @@ pub(super) fn commit_scope_required(commit: &Commit, config: &Conf) -> bool {
 }
 
 pub(super) fn commit_scope_invalid(commit: &Commit, config: &Conf) -> bool {
-    if config.commit_scope.scopes.contains(&"*".to_string()) {
+    let Some(scopes) = config.commit_scope.scopes.allowed(commit.commit_type) else {
         return false;
+    };
+
+    !commit.scope.is_empty() && !commit.scope.iter().any(|scope| scopes.matches(scope))
+}
+
+/// Explains which scopes are valid, naming the commit type when the scopes are keyed by type.
+pub(super) fn scopes_help(commit: &Commit, config: &Conf) -> Option<&'static str> {
+    let scopes = config.commit_scope.scopes.allowed(commit.commit_type)?;
+
+    Some(match config.commit_scope.scopes {
+        Scopes::All(_) => format!("Valid scopes are: {scopes:?}").leak(),
+        Scopes::ByType(_) => format!(
+            "Valid scopes for `{}` commits are: {scopes:?}",
+            commit.commit_type
+        )
+        .leak(),
+    })
+}
+
+pub(super) fn commit_scope_segment_invalid(commit: &Commit, config: &Conf) -> bool {
+    invalid_segment(commit, config).is_some()
+}
+
+/// Finds the first scope segment that is not in `scope.tree`, returning its span and an
+/// explanation of the segments allowed in its place.
+pub(super) fn invalid_segment(commit: &Commit, config: &Conf) -> Option<((usize, usize), String)> {
+    if config.commit_scope.tree.is_empty() {
+        return None;
+    }
+
+    let separator = config.commit_scope.separator.as_deref().unwrap_or_default();
+    let mut offset = commit.scope_span().0;
+
+    for scope in &commit.scope {
+        let segments = config.commit_scope.segments(scope);
+
+        if let Err(InvalidSegment { index, allowed }) =
+            check_segments(&config.commit_scope.tree, &segments)
+        {
+            let start = offset
+                + segments[..index]
+                    .iter()
+                    .map(|segment| segment.len() + separator.len())
+                    .sum::<usize>();
+            let parent = segments[..index].join(separator);
+
+            let help = if index == 0 {
+                format!("Valid top-level scopes are: {allowed:?}")
+            } else if allowed.is_empty() {
+                format!("`{parent}` has no nested scopes")
+            } else {
+                format!("Valid scopes under `{parent}` are: {allowed:?}")
+            };
+
+            return Some(((start, segments[index].len()), help));
+        }
+
+        offset += scope.len() + 1;
     }
 
-    !commit.scope.is_empty()
-        && !commit
-            .scope
-            .iter()
-            .any(|scope| config.commit_scope.scopes.contains(&scope.to_string()))
+    None
 }
 
 pub(super) fn commit_scope_too_short(commit: &Commit, config: &Conf) -> bool {
     commit
         .scope
         .iter()
-        .any(|scope| scope.len() <= config.commit_scope.min_length)
+        .any(|scope| config.length_unit.measure(scope) <= config.commit_scope.min_length)
 }
 
 pub(super) fn commit_scope_too_long(commit: &Commit, config: &Conf) -> bool {
     commit
         .scope
         .iter()
-        .any(|scope| scope.len() >= config.commit_scope.max_length)
+        .any(|scope| config.length_unit.measure(scope) >= config.commit_scope.max_length)
 }
 
 pub(super) fn commit_scope_case_invalid(commit: &Commit, config: &Conf) -> bool {
-    match config.commit_scope.case {
-        Casing::Camel => commit.scope.iter().any(|scope| !scope.is_camel_case()),
-        Casing::Kebab => commit.scope.iter().any(|scope| !scope.is_kebab_case()),
-        Casing::Pascal => commit.scope.iter().any(|scope| !scope.is_pascal_case()),
-        Casing::Snake => commit.scope.iter().any(|scope| !scope.is_snake_case()),
+    commit
+        .scope
+        .iter()
+        .flat_map(|scope| config.commit_scope.segments(scope))
+        .any(|segment| !config.commit_scope.case.matches(segment))
+}
+
+pub(super) fn fix_commit_scope_case(commit: &mut Commit<'static>, config: &Conf) {
+    let separator = config.commit_scope.separator.as_deref().unwrap_or_default();
+
+    for scope in commit.scope.iter_mut() {
+        *scope = config
+            .commit_scope
+            .segments(scope)
+            .into_iter()
+            .map(|segment| {
+                config
+                    .commit_scope
+                    .case
+                    .convert(segment)
+                    .unwrap_or_else(|| segment.to_string())
+            })
+            .collect::<Vec<_>>()
+            .join(separator)
+            .leak();
     }
 }
 
@@ -81,6 +264,8 @@ mod tests {
             scope: vec![],
             breaking_change: false,
             subject: "subject",
+            prefix: None,
+            suffix: None,
             body: None,
             footer: Default::default(),
             source: "fix subject".to_string(),
@@ -115,7 +300,7 @@ mod tests {
         commit.scope = vec!["invalid"];
 
         let mut config = Conf::default();
-        config.commit_scope.scopes = vec!["fix".to_string()];
+        config.commit_scope.scopes = vec!["fix".to_string()].into();
 
         assert!(commit_scope_invalid(&commit, &config));
     }
@@ -126,7 +311,7 @@ mod tests {
         commit.scope = vec!["fix"];
 
         let mut config = Conf::default();
-        config.commit_scope.scopes = vec!["fix".to_string()];
+        config.commit_scope.scopes = vec!["fix".to_string()].into();
 
         assert!(!commit_scope_invalid(&commit, &config));
     }
@@ -137,11 +322,133 @@ mod tests {
         commit.scope = vec!["fix"];
 
         let mut config = Conf::default();
-        config.commit_scope.scopes = vec!["*".to_string()];
+        config.commit_scope.scopes = vec!["*".to_string()].into();
+
+        assert!(!commit_scope_invalid(&commit, &config));
+    }
+
+    #[test]
+    fn test_regex() {
+        let mut commit = sample_commit();
+        commit.scope = vec!["api/v2"];
+
+        let mut config = Conf::default();
+        config.commit_scope.scopes = vec!["^api/v[0-9]+$".to_string()].into();
+
+        assert!(!commit_scope_invalid(&commit, &config));
+
+        commit.scope = vec!["api/next"];
+        assert!(commit_scope_invalid(&commit, &config));
+    }
+
+    #[test]
+    fn test_by_type() {
+        let mut commit = sample_commit();
+        commit.commit_type = "ci";
+        commit.scope = vec!["core"];
+
+        let mut config = Conf::default();
+        config.commit_scope.scopes = Scopes::ByType(BTreeMap::from([
+            ("ci".to_string(), vec!["github".to_string()].into()),
+            ("*".to_string(), vec!["core".to_string()].into()),
+        ]));
+
+        assert!(commit_scope_invalid(&commit, &config));
+
+        commit.scope = vec!["github"];
+        assert!(!commit_scope_invalid(&commit, &config));
+
+        commit.commit_type = "feat";
+        assert!(commit_scope_invalid(&commit, &config));
+
+        commit.scope = vec!["core"];
+        assert!(!commit_scope_invalid(&commit, &config));
+    }
+
+    #[test]
+    fn test_by_type_unlisted() {
+        let mut commit = sample_commit();
+        commit.commit_type = "docs";
+        commit.scope = vec!["anything"];
+
+        let mut config = Conf::default();
+        config.commit_scope.scopes = Scopes::ByType(BTreeMap::from([(
+            "ci".to_string(),
+            vec!["github".to_string()].into(),
+        )]));
 
         assert!(!commit_scope_invalid(&commit, &config));
     }
 
+    #[test]
+    fn test_scopes_help() {
+        let mut commit = sample_commit();
+        commit.commit_type = "ci";
+
+        let mut config = Conf::default();
+        assert_eq!(scopes_help(&commit, &config), None);
+
+        config.commit_scope.scopes = Scopes::ByType(BTreeMap::from([(
+            "ci".to_string(),
+            vec!["github".to_string(), "gitlab".to_string()].into(),
+        )]));
+
+        assert_eq!(
+            scopes_help(&commit, &config),
+            Some("Valid scopes for `ci` commits are: [\"github\", \"gitlab\"]")
+        );
+    }
+
+    fn hierarchical_config() -> Conf {
+        let mut config = Conf::default();
+        config.commit_scope.separator = Some("/".to_string());
+        config.commit_scope.tree =
+            toml::from_str("api = [\"auth\"]\n\n[ui.settings]\nprofile = []\n").unwrap();
+
+        config
+    }
+
+    #[test]
+    fn test_segment_valid() {
+        let mut commit = sample_commit();
+        commit.scope = vec!["api/auth", "ui/settings/profile"];
+
+        assert!(!commit_scope_segment_invalid(
+            &commit,
+            &hierarchical_config()
+        ));
+    }
+
+    #[test]
+    fn test_segment_invalid() {
+        let mut commit = sample_commit();
+        commit.scope = vec!["api/auth", "ui/account"];
+        commit.source = "fix(api/auth,ui/account): subject".to_string();
+
+        assert_eq!(
+            invalid_segment(&commit, &hierarchical_config()),
+            Some((
+                (16, 7),
+                "Valid scopes under `ui` are: [\"settings\"]".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_segment_case() {
+        let mut commit = sample_commit();
+        commit.scope = vec!["api/user-settings"];
+
+        let config = hierarchical_config();
+        assert!(!commit_scope_case_invalid(&commit, &config));
+
+        commit.scope = vec!["api/userSettings"];
+        assert!(commit_scope_case_invalid(&commit, &config));
+
+        fix_commit_scope_case(&mut commit, &config);
+        assert_eq!(commit.scope, vec!["api/user-settings"]);
+    }
+
     #[test]
     fn test_too_short() {
         let mut commit = sample_commit();
@@ -190,4 +497,24 @@ mod tests {
 
         assert!(!commit_scope_case_invalid(&commit, &Conf::default()));
     }
+
+    #[test]
+    fn test_fix_case() {
+        let mut commit = sample_commit();
+        commit.scope = vec!["snake_case", "PascalCase"];
+
+        fix_commit_scope_case(&mut commit, &Conf::default());
+
+        assert_eq!(commit.scope, vec!["snake-case", "pascal-case"]);
+    }
+
+    #[test]
+    fn test_fix_case_keeps_non_ascii_scope() {
+        let mut commit = sample_commit();
+        commit.scope = vec!["ünï-çà", "Ui"];
+
+        fix_commit_scope_case(&mut commit, &Conf::default());
+
+        assert_eq!(commit.scope, vec!["ünï-çà", "ui"]);
+    }
 }
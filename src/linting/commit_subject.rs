@@ -0,0 +1,115 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::Commit;
+
+use super::{Cases, Casing, Conf};
+
+/// Rules for the commit subject.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub(crate) struct SubjectConf {
+    /// Whether the subject may end with a full stop.
+    pub full_stop: bool,
+    /// Case of the subject, or a list of accepted cases.
+    pub case: Cases,
+}
+
+impl Default for SubjectConf {
+    fn default() -> Self {
+        Self {
+            full_stop: true,
+            case: Casing::Any.into(),
+        }
+    }
+}
+
+pub(super) fn commit_subject_full_stop(commit: &Commit, config: &Conf) -> bool {
+    !config.commit_subject.full_stop && commit.subject.trim_end().ends_with('.')
+}
+
+pub(super) fn commit_subject_case_invalid(commit: &Commit, config: &Conf) -> bool {
+    !config
+        .commit_subject
+        .case
+        .matches(commit.subject.trim_end())
+}
+
+pub(super) fn fix_commit_subject_full_stop(commit: &mut Commit<'static>, _config: &Conf) {
+    let trimmed = commit.subject.trim_end();
+    let trailing = &commit.subject[trimmed.len()..];
+
+    commit.subject = format!("{}{trailing}", trimmed.trim_end_matches('.')).leak();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_commit() -> Commit<'static> {
+        Commit {
+            commit_type: "fix",
+            scope: vec![],
+            breaking_change: false,
+            subject: "subject",
+            prefix: None,
+            suffix: None,
+            body: None,
+            footer: Default::default(),
+            source: "fix subject".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_full_stop() {
+        let mut commit = sample_commit();
+        commit.subject = "subject.";
+
+        let mut config = Conf::default();
+        config.commit_subject.full_stop = false;
+
+        assert!(commit_subject_full_stop(&commit, &config));
+    }
+
+    #[test]
+    fn test_no_full_stop() {
+        let mut commit = sample_commit();
+        commit.subject = "subject";
+
+        assert!(!commit_subject_full_stop(&commit, &Conf::default()));
+    }
+
+    #[test]
+    fn test_full_stop_allowed() {
+        let mut commit = sample_commit();
+        commit.subject = "subject.";
+
+        assert!(!commit_subject_full_stop(&commit, &Conf::default()));
+    }
+
+    #[test]
+    fn test_case() {
+        let mut commit = sample_commit();
+        commit.subject = "Add the thing";
+
+        assert!(!commit_subject_case_invalid(&commit, &Conf::default()));
+
+        let mut config = Conf::default();
+        config.commit_subject.case = Cases::Many(vec![Casing::Lower, Casing::Sentence]);
+
+        assert!(!commit_subject_case_invalid(&commit, &config));
+
+        commit.subject = "Add The Thing";
+        assert!(commit_subject_case_invalid(&commit, &config));
+    }
+
+    #[test]
+    fn test_fix_full_stop() {
+        let mut commit = sample_commit();
+        commit.subject = "subject.\n";
+
+        fix_commit_subject_full_stop(&mut commit, &Conf::default());
+
+        assert_eq!(commit.subject, "subject\n");
+    }
+}
@@ -1,62 +1,64 @@
-use cruet::Inflector;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::Commit;
 
-use super::{Casing, Conf};
+use super::{Cases, Conf, Patterns};
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "kebab-case")]
+/// Rules for the commit type.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
 pub(crate) struct TypeConf {
+    /// Allowed commit types. Entries starting with `^` or containing `\`, `+`, `(`, `)`, `|` or
+    /// `$` are regular expressions, and entries containing `*` or `?` are globs. `*` matches any
+    /// type.
     #[serde(rename = "enum")]
-    pub types: Vec<String>,
+    pub types: Patterns,
+    /// Minimum length of the commit type.
     pub min_length: usize,
+    /// Maximum length of the commit type.
     pub max_length: usize,
-    pub case: Casing,
+    /// Case of the commit type, or a list of accepted cases.
+    pub case: Cases,
 }
 
 impl Default for TypeConf {
     fn default() -> Self {
         Self {
-            types: vec!["*".to_string()],
+            types: vec!["*".to_string()].into(),
             min_length: usize::MIN,
             max_length: u32::MAX as usize,
-            case: Casing::default(),
+            case: Cases::default(),
         }
     }
 }
 
 pub(super) fn commit_type_invalid(commit: &Commit, config: &Conf) -> bool {
-    if config.commit_type.types.contains(&"*".to_string()) {
-        false
-    } else {
-        !config
-            .commit_type
-            .types
-            .contains(&commit.commit_type.to_string())
-    }
+    !config.commit_type.types.matches(commit.commit_type)
 }
 
 pub(super) fn commit_type_case_invalid(commit: &Commit, config: &Conf) -> bool {
-    match config.commit_type.case {
-        Casing::Camel => !commit.commit_type.is_camel_case(),
-        Casing::Kebab => !commit.commit_type.is_kebab_case(),
-        Casing::Pascal => !commit.commit_type.is_pascal_case(),
-        Casing::Snake => !commit.commit_type.is_snake_case(),
+    !config.commit_type.case.matches(commit.commit_type)
+}
+
+pub(super) fn fix_commit_type_case(commit: &mut Commit<'static>, config: &Conf) {
+    if let Some(commit_type) = config.commit_type.case.convert(commit.commit_type) {
+        commit.commit_type = commit_type.leak();
     }
 }
 
 pub(super) fn commit_type_too_short(commit: &Commit, config: &Conf) -> bool {
-    commit.commit_type.len() <= config.commit_type.min_length
+    config.length_unit.measure(commit.commit_type) <= config.commit_type.min_length
 }
 
 pub(super) fn commit_type_too_long(commit: &Commit, config: &Conf) -> bool {
-    commit.commit_type.len() >= config.commit_type.max_length
+    config.length_unit.measure(commit.commit_type) >= config.commit_type.max_length
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::linting::LengthUnit;
 
     fn sample_commit() -> Commit<'static> {
         Commit {
@@ -64,6 +66,8 @@ mod tests {
             scope: vec![],
             breaking_change: false,
             subject: "subject",
+            prefix: None,
+            suffix: None,
             body: None,
             footer: Default::default(),
             source: "fix subject".to_string(),
@@ -76,7 +80,7 @@ mod tests {
         commit.commit_type = "invalid";
 
         let mut config = Conf::default();
-        config.commit_type.types = vec![];
+        config.commit_type.types = vec![].into();
 
         assert!(commit_type_invalid(&commit, &config));
     }
@@ -87,7 +91,7 @@ mod tests {
         commit.commit_type = "fix";
 
         let mut config = Conf::default();
-        config.commit_type.types = vec!["fix".to_string()];
+        config.commit_type.types = vec!["fix".to_string()].into();
 
         assert!(!commit_type_invalid(&commit, &config));
     }
@@ -100,6 +104,20 @@ mod tests {
         assert!(!commit_type_invalid(&commit, &Conf::default()));
     }
 
+    #[test]
+    fn test_pattern() {
+        let mut commit = sample_commit();
+        commit.commit_type = "deps-dev";
+
+        let mut config = Conf::default();
+        config.commit_type.types = vec!["fix".to_string(), "deps-*".to_string()].into();
+
+        assert!(!commit_type_invalid(&commit, &config));
+
+        commit.commit_type = "deps";
+        assert!(commit_type_invalid(&commit, &config));
+    }
+
     #[test]
     fn test_too_short() {
         let mut commit = sample_commit();
@@ -144,6 +162,24 @@ mod tests {
         assert!(!commit_type_too_long(&commit, &config));
     }
 
+    #[test]
+    fn test_length_unit() {
+        let mut commit = sample_commit();
+        commit.commit_type = "fe\u{301}at";
+
+        let mut config = Conf::default();
+        config.commit_type.max_length = 5;
+
+        config.length_unit = LengthUnit::Graphemes;
+        assert!(!commit_type_too_long(&commit, &config));
+
+        config.length_unit = LengthUnit::Chars;
+        assert!(commit_type_too_long(&commit, &config));
+
+        config.length_unit = LengthUnit::Bytes;
+        assert!(commit_type_too_long(&commit, &config));
+    }
+
     #[test]
     fn test_case_invalid() {
         let mut commit = sample_commit();
@@ -159,4 +195,14 @@ mod tests {
 
         assert!(!commit_type_case_invalid(&commit, &Conf::default()));
     }
+
+    #[test]
+    fn test_fix_case() {
+        let mut commit = sample_commit();
+        commit.commit_type = "Feat";
+
+        fix_commit_type_case(&mut commit, &Conf::default());
+
+        assert_eq!(commit.commit_type, "feat");
+    }
 }
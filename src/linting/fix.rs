@@ -0,0 +1,135 @@
+use crate::{
+    parsing::{missing_separator_space, Grammar},
+    Commit,
+};
+
+/// Applies the fixes that have to happen before the message can be parsed. Returns the normalized
+/// message, and a description of every fix that was applied.
+pub(super) fn normalize(message: &str, grammar: &Grammar) -> (String, Vec<&'static str>) {
+    let mut message = message.to_string();
+    let mut fixed = Vec::new();
+
+    if message.contains("\r\n") {
+        message = message.replace("\r\n", "\n");
+        fixed.push("Converted CRLF line endings to LF");
+    }
+
+    if let Some(offset) = missing_separator_space(&message, grammar) {
+        message.insert(offset, ' ');
+        fixed.push("Inserted a space after the `:` in the header");
+    }
+
+    (message, fixed)
+}
+
+/// Writes the fixed parts of `fixed` back into the source of `original`, leaving the rest of the
+/// message untouched.
+pub(super) fn render(original: &Commit, fixed: &Commit) -> String {
+    let mut message = original.source.clone();
+
+    // From the last footer, so the offsets of the ones before stay valid
+    for (footer, renamed) in original.footer.iter().zip(&fixed.footer).rev() {
+        if footer.key != renamed.key {
+            message.replace_range(footer.offset..footer.offset + footer.key.len(), renamed.key);
+        }
+    }
+
+    if original.subject != fixed.subject {
+        let (start, len) = original.subject_span();
+        message.replace_range(start..start + len, fixed.subject);
+    }
+
+    if original.scope != fixed.scope {
+        let (start, len) = original.scope_span();
+        message.replace_range(start..start + len, &fixed.scope.join(","));
+    }
+
+    if original.commit_type != fixed.commit_type {
+        let (start, len) = original.type_span();
+        message.replace_range(start..start + len, fixed.commit_type);
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::parse;
+
+    #[test]
+    fn test_normalize_crlf() {
+        let (message, fixed) = normalize("fix: subject\r\n\r\nbody", &Grammar::default());
+
+        assert_eq!(message, "fix: subject\n\nbody");
+        assert_eq!(fixed.len(), 1);
+    }
+
+    #[test]
+    fn test_normalize_separator() {
+        let (message, fixed) = normalize("fix(ui):subject", &Grammar::default());
+
+        assert_eq!(message, "fix(ui): subject");
+        assert_eq!(fixed.len(), 1);
+    }
+
+    #[test]
+    fn test_normalize_valid() {
+        let (message, fixed) = normalize("fix(ui): subject", &Grammar::default());
+
+        assert_eq!(message, "fix(ui): subject");
+        assert!(fixed.is_empty());
+    }
+
+    #[test]
+    fn test_normalize_separator_after_prefix() {
+        let grammar = Grammar::new(Some("[A-Z]+-[0-9]+"), None).unwrap();
+        let (message, fixed) = normalize("PROJ-1 fix(ui):subject", &grammar);
+
+        assert_eq!(message, "PROJ-1 fix(ui): subject");
+        assert_eq!(fixed.len(), 1);
+    }
+
+    #[test]
+    fn test_render() {
+        let original = parse("Fix(Ui)!: subject.\n\nbody\n\nRefs: #1").unwrap();
+
+        let mut fixed = original.clone();
+        fixed.commit_type = "fix";
+        fixed.scope = vec!["ui"];
+        fixed.subject = "subject";
+
+        assert_eq!(
+            render(&original, &fixed),
+            "fix(ui)!: subject\n\nbody\n\nRefs: #1"
+        );
+    }
+
+    #[test]
+    fn test_render_footer_key() {
+        let original = parse("fix: subject\n\nbody\n\nbreaking-change: yes\nRefs: #1").unwrap();
+
+        let mut fixed = original.clone();
+        fixed.footer[0].key = "BREAKING-CHANGE";
+
+        assert_eq!(
+            render(&original, &fixed),
+            "fix: subject\n\nbody\n\nBREAKING-CHANGE: yes\nRefs: #1"
+        );
+    }
+
+    #[test]
+    fn test_render_footer_key_in_body() {
+        let original =
+            parse("fix: subject\n\nbody\nbreaking change in a sentence\n\nbreaking change: yes")
+                .unwrap();
+
+        let mut fixed = original.clone();
+        fixed.footer[0].key = "BREAKING CHANGE";
+
+        assert_eq!(
+            render(&original, &fixed),
+            "fix: subject\n\nbody\nbreaking change in a sentence\n\nBREAKING CHANGE: yes"
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use std::fmt::Display;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// The unit the length rules are measured in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum LengthUnit {
+    /// Bytes of the UTF-8 encoding
+    Bytes,
+    /// Unicode scalar values
+    #[default]
+    Chars,
+    /// Extended grapheme clusters, i.e. user-perceived characters
+    Graphemes,
+    /// Columns in a terminal, counting wide characters like CJK and emoji as two
+    Width,
+}
+
+impl Display for LengthUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LengthUnit::Bytes => write!(f, "bytes"),
+            LengthUnit::Chars => write!(f, "characters"),
+            LengthUnit::Graphemes => write!(f, "graphemes"),
+            LengthUnit::Width => write!(f, "columns"),
+        }
+    }
+}
+
+impl LengthUnit {
+    /// The length of the input in this unit.
+    pub(crate) fn measure(&self, input: &str) -> usize {
+        match self {
+            LengthUnit::Bytes => input.len(),
+            LengthUnit::Chars => input.chars().count(),
+            LengthUnit::Graphemes => input.graphemes(true).count(),
+            LengthUnit::Width => input.width(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii() {
+        for unit in [
+            LengthUnit::Bytes,
+            LengthUnit::Chars,
+            LengthUnit::Graphemes,
+            LengthUnit::Width,
+        ] {
+            assert_eq!(unit.measure("feat"), 4);
+        }
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        // `e` followed by a combining acute accent
+        let input = "cafe\u{301}";
+
+        assert_eq!(LengthUnit::Bytes.measure(input), 6);
+        assert_eq!(LengthUnit::Chars.measure(input), 5);
+        assert_eq!(LengthUnit::Graphemes.measure(input), 4);
+        assert_eq!(LengthUnit::Width.measure(input), 4);
+    }
+
+    #[test]
+    fn test_wide() {
+        assert_eq!(LengthUnit::Chars.measure("文档"), 2);
+        assert_eq!(LengthUnit::Width.measure("文档"), 4);
+    }
+}
@@ -1,20 +1,45 @@
-use std::{fmt::Display, path::PathBuf};
+use std::{collections::BTreeMap, fmt::Display};
 
-use figment::{
-    providers::{Format, Serialized, Toml},
-    Figment,
+use cruet::Inflector;
+use miette::{Diagnostic, Report, Severity, SourceSpan};
+use schemars::JsonSchema;
+use serde::{
+    de::{self, IntoDeserializer, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
 };
-use miette::{Context, Diagnostic, IntoDiagnostic, Report, SourceSpan};
-use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::parsing::parse;
+use crate::git;
+use crate::parsing::parse_with;
 use crate::Commit;
+use commit_body::*;
+use commit_breaking::*;
+use commit_footer::*;
+use commit_header::*;
 use commit_scope::*;
+use commit_subject::*;
 use commit_type::*;
+use type_overrides::TypeOverrides;
 
+pub(crate) use commit_header::{AffixConf, Presence};
+pub(crate) use commit_scope::{ScopeSource, Scopes};
+pub(crate) use length::LengthUnit;
+pub(crate) use pattern::{is_pattern, literal_entry, Pattern, Patterns};
+pub(crate) use scope_paths::path_glob;
+
+mod commit_body;
+mod commit_breaking;
+mod commit_footer;
+mod commit_header;
 mod commit_scope;
+mod commit_subject;
 mod commit_type;
+mod fix;
+mod length;
+mod pattern;
+mod scope_paths;
+mod scope_tree;
+mod type_overrides;
 
 #[derive(Debug, Diagnostic, Error)]
 #[error("{kind}")]
@@ -31,6 +56,15 @@ struct LintError {
 
 #[derive(Debug, Diagnostic, Error)]
 enum LintErrorKind {
+    #[error("A header prefix is required")]
+    HeaderPrefixRequired,
+    #[error("The header has a forbidden prefix")]
+    HeaderPrefixForbidden,
+    #[error("A header suffix is required")]
+    HeaderSuffixRequired,
+    #[error("The header has a forbidden suffix")]
+    HeaderSuffixForbidden,
+
     #[error("Invalid commit type")]
     TypeInvalid,
     #[error("The commit type is too short")]
@@ -50,27 +84,134 @@ enum LintErrorKind {
     ScopeTooLong,
     #[error("Invalid commit scope case")]
     ScopeCaseInvalid,
+    #[error("Invalid scope segment")]
+    ScopeSegmentInvalid,
+    #[error("The scope does not match the changed files")]
+    ScopePathsMismatch,
+
+    #[error("The subject ends with a full stop")]
+    SubjectFullStop,
+    #[error("Invalid subject case")]
+    SubjectCaseInvalid,
+
+    #[error("A body is required")]
+    BodyRequired,
+
+    #[error("Breaking changes are not allowed")]
+    BreakingForbidden,
+    #[error("Breaking changes are not allowed on this branch")]
+    BreakingBranchForbidden,
+    #[error("A `BREAKING CHANGE` footer is required")]
+    BreakingFooterRequired,
+    #[error("The breaking change description is too short")]
+    BreakingDescriptionTooShort,
+
+    #[error("Missing required footer")]
+    FooterRequired,
+    #[error("Invalid footer key case")]
+    FooterKeyCaseInvalid,
+    #[error("Misspelled breaking change token")]
+    FooterTokenInvalid,
+}
+
+/// The part of a commit message a rule applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Section {
+    Header,
+    Type,
+    Scope,
+    Subject,
+    Body,
+    Footer,
+    Breaking,
+}
+
+impl LintErrorKind {
+    fn section(&self) -> Section {
+        match self {
+            LintErrorKind::HeaderPrefixRequired
+            | LintErrorKind::HeaderPrefixForbidden
+            | LintErrorKind::HeaderSuffixRequired
+            | LintErrorKind::HeaderSuffixForbidden => Section::Header,
+            LintErrorKind::TypeInvalid
+            | LintErrorKind::TypeTooShort
+            | LintErrorKind::TypeTooLong
+            | LintErrorKind::TypeCaseInvalid => Section::Type,
+            LintErrorKind::ScopeRequired
+            | LintErrorKind::ScopeInvalid
+            | LintErrorKind::ScopeTooShort
+            | LintErrorKind::ScopeTooLong
+            | LintErrorKind::ScopeCaseInvalid
+            | LintErrorKind::ScopeSegmentInvalid
+            | LintErrorKind::ScopePathsMismatch => Section::Scope,
+            LintErrorKind::SubjectFullStop | LintErrorKind::SubjectCaseInvalid => Section::Subject,
+            LintErrorKind::BodyRequired => Section::Body,
+            LintErrorKind::FooterRequired
+            | LintErrorKind::FooterKeyCaseInvalid
+            | LintErrorKind::FooterTokenInvalid => Section::Footer,
+            LintErrorKind::BreakingForbidden
+            | LintErrorKind::BreakingBranchForbidden
+            | LintErrorKind::BreakingFooterRequired
+            | LintErrorKind::BreakingDescriptionTooShort => Section::Breaking,
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+/// Configuration of change-scribe.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
 pub(crate) struct Conf {
+    /// Presets and configuration files to extend. Later entries take precedence, and the
+    /// configuration itself takes precedence over all of them.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) extends: Vec<String>,
+    /// The unit `min-length` and `max-length` are measured in.
+    #[serde(rename = "length-unit")]
+    pub(crate) length_unit: LengthUnit,
+    #[serde(rename = "header")]
+    pub(crate) commit_header: HeaderConf,
     #[serde(rename = "type")]
-    commit_type: TypeConf,
+    pub(crate) commit_type: TypeConf,
     #[serde(rename = "scope")]
-    commit_scope: ScopeConf,
+    pub(crate) commit_scope: ScopeConf,
+    #[serde(rename = "subject")]
+    pub(crate) commit_subject: SubjectConf,
+    #[serde(rename = "body")]
+    pub(crate) commit_body: BodyConf,
+    #[serde(rename = "footer")]
+    pub(crate) commit_footer: FooterConf,
+    #[serde(rename = "breaking")]
+    pub(crate) commit_breaking: BreakingConf,
+    /// Rules per commit type, overriding the scope, subject, body, footer and breaking change rules
+    /// for commits of that type.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) types: BTreeMap<String, TypeOverrides>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub(crate) enum Casing {
-    // camelCase
+    /// camelCase
     Camel,
-    // kebab-case
+    /// kebab-case
+    #[default]
     Kebab,
-    // PascalCase
+    /// PascalCase
     Pascal,
-    // snake_case
+    /// snake_case
     Snake,
+    /// lower case, without any uppercase letters
+    Lower,
+    /// UPPER CASE, without any lowercase letters
+    Upper,
+    /// Sentence case, with only the first letter in uppercase
+    Sentence,
+    /// Title Case, with the first letter of each word in uppercase and the others in lowercase
+    Title,
+    /// Start Case, with the first letter of each word in uppercase
+    Start,
+    /// Any case
+    Any,
 }
 
 impl Display for Casing {
@@ -80,51 +221,405 @@ impl Display for Casing {
             Casing::Kebab => write!(f, "kebab-case"),
             Casing::Pascal => write!(f, "PascalCase"),
             Casing::Snake => write!(f, "snake_case"),
+            Casing::Lower => write!(f, "lower case"),
+            Casing::Upper => write!(f, "UPPER CASE"),
+            Casing::Sentence => write!(f, "Sentence case"),
+            Casing::Title => write!(f, "Title Case"),
+            Casing::Start => write!(f, "Start Case"),
+            Casing::Any => write!(f, "any case"),
+        }
+    }
+}
+
+impl Casing {
+    pub(crate) const ALL: [Casing; 10] = [
+        Casing::Camel,
+        Casing::Kebab,
+        Casing::Pascal,
+        Casing::Snake,
+        Casing::Lower,
+        Casing::Upper,
+        Casing::Sentence,
+        Casing::Title,
+        Casing::Start,
+        Casing::Any,
+    ];
+
+    /// Checks whether the input is in this casing.
+    pub(crate) fn matches(&self, input: &str) -> bool {
+        match self {
+            Casing::Camel => input.is_camel_case(),
+            Casing::Kebab => input.is_kebab_case(),
+            Casing::Pascal => input.is_pascal_case(),
+            Casing::Snake => input.is_snake_case(),
+            Casing::Lower
+            | Casing::Upper
+            | Casing::Sentence
+            | Casing::Title
+            | Casing::Start
+            | Casing::Any => self.convert(input) == input,
+        }
+    }
+
+    /// Converts the input to this casing.
+    fn convert(&self, input: &str) -> String {
+        match self {
+            Casing::Camel => input.to_camel_case(),
+            Casing::Kebab => input.to_kebab_case(),
+            Casing::Pascal => input.to_pascal_case(),
+            Casing::Snake => input.to_snake_case(),
+            Casing::Lower => input.to_lowercase(),
+            Casing::Upper => input.to_uppercase(),
+            Casing::Sentence => upper_first(&input.to_lowercase()),
+            Casing::Title => words(input, |word| upper_first(&word.to_lowercase())),
+            Casing::Start => words(input, upper_first),
+            Casing::Any => input.to_string(),
+        }
+    }
+}
+
+fn upper_first(input: &str) -> String {
+    let mut chars = input.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Applies `f` to each space separated word of the input.
+fn words(input: &str, f: impl Fn(&str) -> String) -> String {
+    input.split(' ').map(f).collect::<Vec<_>>().join(" ")
+}
+
+/// One or more accepted casings, e.g. `"kebab"` or `["kebab", "upper"]`. A value is valid when
+/// it is in any of them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub(crate) enum Cases {
+    One(Casing),
+    Many(Vec<Casing>),
+}
+
+/// Deserializes like an untagged enum, but keeps the error of [`Casing`] for unknown cases.
+impl<'de> Deserialize<'de> for Cases {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CasesVisitor;
+
+        impl<'de> Visitor<'de> for CasesVisitor {
+            type Value = Cases;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a case or a list of cases")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Cases, E> {
+                Casing::deserialize(value.into_deserializer()).map(Cases::One)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Cases, A::Error> {
+                let mut casings = Vec::new();
+
+                while let Some(casing) = seq.next_element()? {
+                    casings.push(casing);
+                }
+
+                if casings.is_empty() {
+                    return Err(de::Error::invalid_length(0, &self));
+                }
+
+                Ok(Cases::Many(casings))
+            }
         }
+
+        deserializer.deserialize_any(CasesVisitor)
     }
 }
 
-impl Default for Casing {
+impl Default for Cases {
     fn default() -> Self {
-        Self::Kebab
+        Cases::One(Casing::default())
     }
 }
 
-macro_rules! lint_fn {
-    ( $( $rule:ident => $error:expr ),* ) => {
-        pub(crate) fn lint(message: &'static str, config_path: Option<PathBuf>) -> miette::Result<()> {
-            let commit = parse(message)?;
+impl From<Casing> for Cases {
+    fn from(casing: Casing) -> Self {
+        Cases::One(casing)
+    }
+}
+
+impl Display for Cases {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let casings = self
+            .casings()
+            .iter()
+            .map(|casing| format!("`{casing}`"))
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", casings.join(" or "))
+    }
+}
+
+impl Cases {
+    fn casings(&self) -> &[Casing] {
+        match self {
+            Cases::One(casing) => std::slice::from_ref(casing),
+            Cases::Many(casings) => casings,
+        }
+    }
+
+    /// Checks whether the input is in any of the casings.
+    pub(crate) fn matches(&self, input: &str) -> bool {
+        self.casings().iter().any(|casing| casing.matches(input))
+    }
+
+    /// Converts the input to the first casing, unless it already is in one of them. Returns
+    /// `None` when the conversion changes more than the case of the words, e.g. when it splits
+    /// `ünï` into `ün-ï`, so the value is left for linting to report.
+    fn convert(&self, input: &str) -> Option<String> {
+        let converted = match self.casings().first() {
+            Some(casing) if !self.matches(input) => casing.convert(input),
+            _ => input.to_string(),
+        };
+
+        (case_words(&converted) == case_words(input)).then_some(converted)
+    }
+}
+
+/// The lowercased words of the input, split at separators and where a lowercase letter is
+/// followed by an uppercase one.
+fn case_words(input: &str) -> Vec<String> {
+    let mut words = vec![String::new()];
+    let mut previous = None::<char>;
+
+    for c in input.chars() {
+        if matches!(c, '-' | '_' | ' ') {
+            words.push(String::new());
+        } else {
+            if c.is_uppercase() && previous.is_some_and(char::is_lowercase) {
+                words.push(String::new());
+            }
+
+            words
+                .last_mut()
+                .expect("there is a word")
+                .extend(c.to_lowercase());
+        }
+
+        previous = Some(c);
+    }
+
+    words.retain(|word| !word.is_empty());
+    words
+}
+
+/// Lints the message, printing each violation. With the files changed by the commit, the scope is
+/// also checked against `scope.paths`. Breaking changes are checked against the branch.
+pub(crate) fn lint(
+    message: &'static str,
+    config: &Conf,
+    files: Option<&[String]>,
+    branch: Option<&str>,
+) -> miette::Result<()> {
+    let reports = diagnose(message, config, files, branch)?;
+
+    for report in &reports {
+        eprintln!("{report:?}");
+    }
+
+    if reports.iter().any(is_error) {
+        miette::bail!("Linting failed")
+    } else {
+        Ok(())
+    }
+}
+
+/// Lints every commit in a revision range, each against the files it changes and the branch.
+pub(crate) fn lint_range(range: &str, config: &Conf, branch: Option<&str>) -> miette::Result<()> {
+    let commits = git::commits(range)?;
+    let mut failed = 0;
+
+    for (hash, message) in &commits {
+        let files = git::changed_files(hash)?;
+
+        let reports = match diagnose(message.clone().leak(), config, Some(&files), branch) {
+            Ok(reports) => reports,
+            Err(report) => vec![report],
+        };
+
+        if reports.is_empty() {
+            continue;
+        }
+
+        eprintln!("Commit {hash:.7}:");
+
+        for report in &reports {
+            eprintln!("{report:?}");
+        }
+
+        if reports.iter().any(is_error) {
+            failed += 1;
+        }
+    }
+
+    if failed == 0 {
+        Ok(())
+    } else {
+        miette::bail!("Linting failed for {failed} of {} commits", commits.len())
+    }
+}
+
+fn diagnose(
+    message: &'static str,
+    config: &Conf,
+    files: Option<&[String]>,
+    branch: Option<&str>,
+) -> miette::Result<Vec<Report>> {
+    let commit = parse_with(message, &config.grammar())?;
+
+    let mut reports = check(&commit, config)
+        .into_iter()
+        .map(Report::from)
+        .collect::<Vec<_>>();
+
+    let config = config.for_type(commit.commit_type);
+
+    if let Some(files) = files {
+        reports.extend(scope_paths::check_paths(&commit, &config, files)?);
+    }
+
+    reports.extend(check_branch(&commit, &config, branch));
+
+    Ok(reports)
+}
+
+fn is_error(report: &Report) -> bool {
+    report.severity().unwrap_or(Severity::Error) == Severity::Error
+}
+
+/// Checks the rules of a single section of the message, returning a description of each violation.
+pub(crate) fn check_section(
+    message: &'static str,
+    config: &Conf,
+    section: Section,
+) -> Result<(), String> {
+    let commit = parse_with(message, &config.grammar()).map_err(|e| e.to_string())?;
+
+    let errors = check(&commit, config)
+        .into_iter()
+        .filter(|error| error.kind.section() == section)
+        .map(|error| match error.help {
+            Some(help) => format!("{}. {help}", error.kind),
+            None => error.kind.to_string(),
+        })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("\n"))
+    }
+}
+
+/// Applies all safe fixes to the message, and returns the fixed message. Rules that could not be
+/// fixed are left for [`lint`] to report.
+pub(crate) fn fix(message: &'static str, config: &Conf) -> miette::Result<String> {
+    let grammar = config.grammar();
+    let (message, normalized) = fix::normalize(message, &grammar);
+
+    for fixed in &normalized {
+        eprintln!("Fixed: {fixed}");
+    }
+
+    let message: &'static str = message.leak();
+    let original = parse_with(message, &grammar)?;
+
+    let config = config.for_type(original.commit_type);
 
-            let config = Figment::new().merge(Serialized::defaults(Conf::default()));
+    let mut commit = original.clone();
+    let fixed = fix_commit(&mut commit, &config);
 
-            let config = if let Some(config_path) = config_path {
-                config.merge(Toml::file(config_path))
-            } else {
-                config.merge(Toml::file("change-scribe.toml")).merge(Toml::file(".change-scribe.toml"))
-            }.extract::<Conf>().into_diagnostic().context("Failed to load configuration")?;
+    for fixed in &fixed {
+        eprintln!("Fixed: {fixed}");
+    }
 
-            let mut errors = Vec::<Report>::new();
+    Ok(fix::render(&original, &commit))
+}
+
+/// Checks all rules, with the overrides for the commit type applied.
+fn check(commit: &Commit, config: &Conf) -> Vec<LintError> {
+    check_rules(commit, &config.for_type(commit.commit_type))
+}
+
+macro_rules! lint_fn {
+    ( $( $rule:ident => $error:expr $( => $fix:ident($kind:ident) )? ),* ) => {
+        fn check_rules(commit: &Commit, config: &Conf) -> Vec<LintError> {
+            let mut errors = Vec::<LintError>::new();
 
             $(
-                $rule(&commit, &config).then(|| {
-                    errors.push($error(&commit, &config).into());
+                $rule(commit, config).then(|| {
+                    errors.push($error(commit, config));
                 });
             )*
 
-            for error in &errors {
-                println!("{error:?}");
-            }
+            errors
+        }
 
-            if errors.is_empty() {
-                Ok(())
-            } else {
-                miette::bail!("Linting failed")
-            }
+        fn fix_commit(commit: &mut Commit<'static>, config: &Conf) -> Vec<LintErrorKind> {
+            let mut fixed = Vec::<LintErrorKind>::new();
+
+            $($(
+                if $rule(commit, config) {
+                    $fix(commit, config);
+
+                    if !$rule(commit, config) {
+                        fixed.push(LintErrorKind::$kind);
+                    }
+                }
+            )?)*
+
+            fixed
         }
     };
 }
 
 lint_fn! {
+    // Header
+    commit_header_prefix_required => |commit: &Commit, config: &Conf| LintError {
+        input: commit.source.clone(),
+        span: commit.prefix_span().into(),
+        label: Some("Insert a prefix before the commit type"),
+        help: Some(format!(
+            "The prefix must match `{}`",
+            config.commit_header.prefix.pattern.as_deref().unwrap_or_default()
+        ).leak()),
+        kind: LintErrorKind::HeaderPrefixRequired,
+    },
+    commit_header_prefix_forbidden => |commit: &Commit, _config: &Conf| LintError {
+        input: commit.source.clone(),
+        span: commit.prefix_span().into(),
+        label: Some("At the prefix"),
+        help: Some("Remove the prefix from the header"),
+        kind: LintErrorKind::HeaderPrefixForbidden,
+    },
+    commit_header_suffix_required => |commit: &Commit, config: &Conf| LintError {
+        input: commit.source.clone(),
+        span: commit.suffix_span().into(),
+        label: Some("Insert a suffix after the subject"),
+        help: Some(format!(
+            "The suffix must match `{}`",
+            config.commit_header.suffix.pattern.as_deref().unwrap_or_default()
+        ).leak()),
+        kind: LintErrorKind::HeaderSuffixRequired,
+    },
+    commit_header_suffix_forbidden => |commit: &Commit, _config: &Conf| LintError {
+        input: commit.source.clone(),
+        span: commit.suffix_span().into(),
+        label: Some("At the suffix"),
+        help: Some("Remove the suffix from the header"),
+        kind: LintErrorKind::HeaderSuffixForbidden,
+    },
+
     // Type
     commit_type_invalid => |commit: &Commit, config: &Conf| LintError {
         input: commit.source.clone(),
@@ -137,23 +632,23 @@ lint_fn! {
         input: commit.source.clone(),
         span: commit.type_span().into(),
         label: Some("At the commit type"),
-        help: Some(format!("The commit type must be at least {} characters long", config.commit_type.min_length).leak()),
+        help: Some(format!("The commit type must be longer than {} {}", config.commit_type.min_length, config.length_unit).leak()),
         kind: LintErrorKind::TypeTooShort,
     },
     commit_type_too_long => |commit: &Commit, config: &Conf| LintError {
         input: commit.source.clone(),
         span: commit.type_span().into(),
         label: Some("At the commit type"),
-        help: Some(format!("The commit type must be at most {} characters long", config.commit_type.max_length).leak()),
+        help: Some(format!("The commit type must be shorter than {} {}", config.commit_type.max_length, config.length_unit).leak()),
         kind: LintErrorKind::TypeTooLong,
     },
     commit_type_case_invalid => |commit: &Commit, config: &Conf| LintError {
         input: commit.source.clone(),
         span: commit.type_span().into(),
         label: Some("At the commit type"),
-        help: Some(format!("The commit type must be in `{}` case", config.commit_type.case).leak()),
+        help: Some(format!("The commit type must be in {}", config.commit_type.case).leak()),
         kind: LintErrorKind::TypeCaseInvalid,
-    },
+    } => fix_commit_type_case(TypeCaseInvalid),
 
     // Scope
     commit_scope_required => |commit: &Commit, config: &Conf| LintError {
@@ -163,35 +658,195 @@ lint_fn! {
             "Insert a scope after the commit type. e.g.: `{}(scope)`",
             commit.commit_type
         ).leak()),
-        help: Some(format!("Valid scopes are: {:?}", config.commit_scope.scopes).leak()),
+        help: scopes_help(commit, config),
         kind: LintErrorKind::ScopeRequired,
     },
     commit_scope_invalid => |commit: &Commit, config: &Conf| LintError {
         input: commit.source.clone(),
         span: commit.scope_span().into(),
         label: None,
-        help: Some(format!("Valid scopes are: {:?}", config.commit_scope.scopes).leak()),
+        help: scopes_help(commit, config),
         kind: LintErrorKind::ScopeInvalid,
     },
+    commit_scope_segment_invalid => |commit: &Commit, config: &Conf| {
+        let (span, help) = invalid_segment(commit, config).expect("the segment is invalid");
+
+        LintError {
+            input: commit.source.clone(),
+            span: span.into(),
+            label: Some("At this segment"),
+            help: Some(help.leak()),
+            kind: LintErrorKind::ScopeSegmentInvalid,
+        }
+    },
     commit_scope_too_short => |commit: &Commit, config: &Conf| LintError {
         input: commit.source.clone(),
         span: commit.scope_span().into(),
         label: None,
-        help: Some(format!("The scope must be at least {} characters long", config.commit_scope.min_length).leak()),
+        help: Some(format!("The scope must be longer than {} {}", config.commit_scope.min_length, config.length_unit).leak()),
         kind: LintErrorKind::ScopeTooShort,
     },
     commit_scope_too_long => |commit: &Commit, config: &Conf| LintError {
         input: commit.source.clone(),
         span: commit.scope_span().into(),
         label: None,
-        help: Some(format!("The scope must be at most {} characters long", config.commit_scope.max_length).leak()),
+        help: Some(format!("The scope must be shorter than {} {}", config.commit_scope.max_length, config.length_unit).leak()),
         kind: LintErrorKind::ScopeTooLong,
     },
     commit_scope_case_invalid => |commit: &Commit, config: &Conf| LintError {
         input: commit.source.clone(),
         span: commit.scope_span().into(),
         label: None,
-        help: Some(format!("The scope must be in `{}` case", config.commit_scope.case).leak()),
+        help: Some(format!("The scope must be in {}", config.commit_scope.case).leak()),
         kind: LintErrorKind::ScopeCaseInvalid,
+    } => fix_commit_scope_case(ScopeCaseInvalid),
+
+    // Subject
+    commit_subject_full_stop => |commit: &Commit, _config: &Conf| LintError {
+        input: commit.source.clone(),
+        span: commit.subject_span().into(),
+        label: Some("At the subject"),
+        help: Some("Remove the trailing `.` from the subject"),
+        kind: LintErrorKind::SubjectFullStop,
+    } => fix_commit_subject_full_stop(SubjectFullStop),
+    commit_subject_case_invalid => |commit: &Commit, config: &Conf| LintError {
+        input: commit.source.clone(),
+        span: commit.subject_span().into(),
+        label: Some("At the subject"),
+        help: Some(format!("The subject must be in {}", config.commit_subject.case).leak()),
+        kind: LintErrorKind::SubjectCaseInvalid,
+    },
+
+    // Body
+    commit_body_required => |commit: &Commit, config: &Conf| LintError {
+        input: commit.source.clone(),
+        span: (commit.header_len(), 0).into(),
+        label: Some("Insert a body after the header, separated by a blank line"),
+        help: Some(if config.is_overridden(commit.commit_type, &["body", "required"]) {
+            format!("Commits of type `{}` must have a body", commit.commit_type).leak()
+        } else {
+            "Commits must have a body"
+        }),
+        kind: LintErrorKind::BodyRequired,
+    },
+
+    // Footer
+    commit_footer_required => |commit: &Commit, config: &Conf| LintError {
+        input: commit.source.clone(),
+        span: (commit.source.len(), 0).into(),
+        label: Some("Insert the footers at the end of the message"),
+        help: Some(format!("Missing footers: {:?}", missing_footers(commit, config)).leak()),
+        kind: LintErrorKind::FooterRequired,
+    },
+    commit_footer_key_case_invalid => |commit: &Commit, config: &Conf| LintError {
+        input: commit.source.clone(),
+        span: invalid_footer_key(commit, config).expect("a footer key is invalid").into(),
+        label: Some("At the footer key"),
+        help: Some(format!("Footer keys must be in {}", config.commit_footer.key_case).leak()),
+        kind: LintErrorKind::FooterKeyCaseInvalid,
+    },
+    commit_footer_token_invalid => |commit: &Commit, config: &Conf| {
+        let (span, spelling) = invalid_footer_token(commit, config)
+            .expect("a breaking change token is misspelled");
+
+        LintError {
+            input: commit.source.clone(),
+            span: span.into(),
+            label: Some("At the footer key"),
+            help: Some(format!("Write the token as `{spelling}`").leak()),
+            kind: LintErrorKind::FooterTokenInvalid,
+        }
+    } => fix_commit_footer_token(FooterTokenInvalid),
+
+    // Breaking change
+    commit_breaking_forbidden => |commit: &Commit, config: &Conf| LintError {
+        input: commit.source.clone(),
+        span: commit.breaking_span().into(),
+        label: Some("This marks a breaking change"),
+        help: Some(if config.is_overridden(commit.commit_type, &["breaking", "allowed"]) {
+            format!("Commits of type `{}` can not contain breaking changes", commit.commit_type).leak()
+        } else {
+            "Breaking changes are not allowed"
+        }),
+        kind: LintErrorKind::BreakingForbidden,
+    },
+    commit_breaking_footer_required => |commit: &Commit, _config: &Conf| LintError {
+        input: commit.source.clone(),
+        span: commit.breaking_span().into(),
+        label: Some("This marks a breaking change"),
+        help: Some("Describe the breaking change in a `BREAKING CHANGE: <description>` footer"),
+        kind: LintErrorKind::BreakingFooterRequired,
+    },
+    commit_breaking_description_too_short => |commit: &Commit, config: &Conf| LintError {
+        input: commit.source.clone(),
+        span: commit.breaking_span().into(),
+        label: Some("At the breaking change"),
+        help: Some(format!(
+            "The description must be longer than {} {}",
+            config.commit_breaking.min_description_length,
+            config.length_unit
+        ).leak()),
+        kind: LintErrorKind::BreakingDescriptionTooShort,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_casings() {
+        assert!(Casing::Lower.matches("add a thing"));
+        assert!(!Casing::Lower.matches("add a Thing"));
+        assert!(Casing::Upper.matches("WIP"));
+        assert!(Casing::Sentence.matches("Add a thing"));
+        assert!(!Casing::Sentence.matches("Add a Thing"));
+        assert!(Casing::Title.matches("Add A Thing"));
+        assert!(!Casing::Title.matches("Add API Support"));
+        assert!(Casing::Start.matches("Add API Support"));
+        assert!(Casing::Any.matches("whatEVER_you like"));
+    }
+
+    #[test]
+    fn test_convert() {
+        assert_eq!(Casing::Sentence.convert("ADD a Thing"), "Add a thing");
+        assert_eq!(Casing::Title.convert("add a THING"), "Add A Thing");
+        assert_eq!(Casing::Start.convert("add an API"), "Add An API");
+    }
+
+    #[test]
+    fn test_cases() {
+        let cases: Cases = toml::Value::try_from(["kebab", "upper"])
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        assert!(cases.matches("wip-thing"));
+        assert!(cases.matches("WIP"));
+        assert!(!cases.matches("Wip"));
+        assert_eq!(cases.convert("Wip").as_deref(), Some("wip"));
+        assert_eq!(cases.to_string(), "`kebab-case` or `UPPER CASE`");
+    }
+
+    #[test]
+    fn test_cases_convert_only_the_case() {
+        let cases = Cases::One(Casing::Kebab);
+
+        assert_eq!(
+            cases.convert("userSettings").as_deref(),
+            Some("user-settings")
+        );
+        assert_eq!(
+            cases.convert("User_Settings").as_deref(),
+            Some("user-settings")
+        );
+        assert_eq!(cases.convert("ünï-çà"), None);
+    }
+
+    #[test]
+    fn test_cases_empty() {
+        let error = toml::from_str::<Conf>("type.case = []\n").unwrap_err();
+
+        assert!(error.message().contains("invalid length 0"));
     }
 }
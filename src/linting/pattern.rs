@@ -0,0 +1,207 @@
+use std::{fmt::Debug, ops::Deref};
+
+use globset::{Glob, GlobMatcher};
+use regex::Regex;
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Characters that only have a meaning in regular expressions, so entries containing them are
+/// regular expressions even without a leading `^`.
+const REGEX_CHARS: [char; 6] = ['\\', '+', '(', ')', '|', '$'];
+
+/// An entry of `type.enum` or `scope.enum`. Entries starting with `^` or containing one of
+/// [`REGEX_CHARS`] are regular expressions, entries containing `*` or `?` are globs, and all other
+/// entries match literally. Every kind of entry matches the whole value.
+#[derive(Debug, Clone)]
+pub(crate) enum Pattern {
+    Literal(String),
+    Glob(GlobMatcher),
+    Regex(Regex),
+}
+
+impl Pattern {
+    pub(crate) fn parse(entry: &str) -> Result<Self, String> {
+        if is_regex(entry) {
+            Regex::new(&format!("^(?:{entry})$"))
+                .map(Pattern::Regex)
+                .map_err(|e| e.to_string())
+        } else if is_pattern(entry) {
+            Glob::new(entry)
+                .map(|glob| Pattern::Glob(glob.compile_matcher()))
+                .map_err(|e| e.kind().to_string())
+        } else {
+            Ok(Pattern::Literal(entry.to_string()))
+        }
+    }
+
+    pub(crate) fn matches(&self, value: &str) -> bool {
+        match self {
+            Pattern::Literal(literal) => literal == value,
+            Pattern::Glob(glob) => glob.is_match(value),
+            Pattern::Regex(regex) => regex.is_match(value),
+        }
+    }
+}
+
+fn is_regex(entry: &str) -> bool {
+    entry.starts_with('^') || entry.contains(REGEX_CHARS)
+}
+
+/// Checks whether the entry is a glob or regular expression, rather than a literal value.
+pub(crate) fn is_pattern(entry: &str) -> bool {
+    is_regex(entry) || entry.contains(['*', '?'])
+}
+
+/// An entry that matches the value literally, as a regular expression if the value itself would
+/// be read as a pattern.
+pub(crate) fn literal_entry(value: &str) -> String {
+    if is_pattern(value) {
+        format!("^{}$", regex::escape(value))
+    } else {
+        value.to_string()
+    }
+}
+
+/// A list of entries, each compiled to a [`Pattern`] once, when the configuration is loaded.
+/// Dereferences to the entries as written.
+#[derive(Clone, Default)]
+pub(crate) struct Patterns {
+    entries: Vec<String>,
+    /// The compiled entries. Invalid patterns are left out, they are reported when the
+    /// configuration is validated.
+    compiled: Vec<Pattern>,
+}
+
+impl Patterns {
+    /// Checks whether the value matches any of the entries.
+    pub(crate) fn matches(&self, value: &str) -> bool {
+        self.compiled.iter().any(|pattern| pattern.matches(value))
+    }
+}
+
+impl From<Vec<String>> for Patterns {
+    fn from(entries: Vec<String>) -> Self {
+        let compiled = entries
+            .iter()
+            .filter_map(|entry| Pattern::parse(entry).ok())
+            .collect();
+
+        Self { entries, compiled }
+    }
+}
+
+impl Deref for Patterns {
+    type Target = [String];
+
+    fn deref(&self) -> &Self::Target {
+        &self.entries
+    }
+}
+
+impl Debug for Patterns {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.entries.fmt(f)
+    }
+}
+
+impl PartialEq for Patterns {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries
+    }
+}
+
+impl Serialize for Patterns {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.entries.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Patterns {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<String>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl JsonSchema for Patterns {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        Vec::<String>::schema_name()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        Vec::<String>::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(entries: &[&str]) -> Patterns {
+        entries
+            .iter()
+            .map(|entry| entry.to_string())
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    #[test]
+    fn test_literal() {
+        assert!(patterns(&["core"]).matches("core"));
+        assert!(!patterns(&["core"]).matches("core-ui"));
+    }
+
+    #[test]
+    fn test_glob() {
+        let patterns = patterns(&["deps-*", "v?"]);
+
+        assert!(patterns.matches("deps-dev"));
+        assert!(patterns.matches("v2"));
+        assert!(!patterns.matches("deps"));
+        assert!(!patterns.matches("v10"));
+    }
+
+    #[test]
+    fn test_wildcard() {
+        assert!(patterns(&["*"]).matches("api/v1"));
+    }
+
+    #[test]
+    fn test_regex() {
+        let patterns = patterns(&["^api/v[0-9]+$", r"^JIRA-\d+$"]);
+
+        assert!(patterns.matches("api/v12"));
+        assert!(patterns.matches("JIRA-123"));
+        assert!(!patterns.matches("api/v1beta"));
+        assert!(!patterns.matches("JIRA-"));
+    }
+
+    #[test]
+    fn test_unanchored_regex() {
+        let patterns = patterns(&[r"JIRA-\d+", "feat|fix"]);
+
+        assert!(is_pattern(r"JIRA-\d+"));
+        assert!(patterns.matches("JIRA-123"));
+        assert!(patterns.matches("fix"));
+        assert!(!patterns.matches(r"JIRA-\d+"));
+        assert!(!patterns.matches("JIRA-"));
+        assert!(!patterns.matches("hotfix"));
+        assert!(!patterns.matches("xJIRA-1zz"));
+    }
+
+    #[test]
+    fn test_literal_entry() {
+        assert_eq!(literal_entry("core"), "core");
+        assert_eq!(literal_entry("c++"), r"^c\+\+$");
+        assert!(patterns(&[&literal_entry("c++")]).matches("c++"));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        assert!(Pattern::parse("^api/(").is_err());
+        assert!(!patterns(&["^api/("]).matches("api/("));
+    }
+}
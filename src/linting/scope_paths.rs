@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use miette::{Diagnostic, IntoDiagnostic, SourceSpan, WrapErr};
+use thiserror::Error;
+
+use crate::Commit;
+
+use super::{Conf, LintError, LintErrorKind};
+
+#[derive(Debug, Diagnostic, Error)]
+#[error("The changed files suggest a scope")]
+#[diagnostic(severity(Warning))]
+struct ScopeSuggestion {
+    #[source_code]
+    input: String,
+    #[label("Insert a scope after the commit type")]
+    span: SourceSpan,
+    #[help]
+    help: String,
+}
+
+/// Checks the scope of a commit against the files it changes, using `scope.paths`. Declared
+/// scopes that cover none of the files are errors. Without a scope, the scopes the files imply
+/// are suggested as a warning.
+pub(super) fn check_paths(
+    commit: &Commit,
+    config: &Conf,
+    files: &[String],
+) -> miette::Result<Vec<miette::Report>> {
+    if files.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let sets = config
+        .commit_scope
+        .paths
+        .iter()
+        .map(|(scope, globs)| Ok((scope.as_str(), glob_set(scope, globs)?)))
+        .collect::<miette::Result<BTreeMap<_, _>>>()?;
+
+    let implied = sets
+        .iter()
+        .filter(|(_, set)| files.iter().any(|file| set.is_match(file)))
+        .map(|(scope, _)| *scope)
+        .collect::<Vec<_>>();
+
+    if commit.scope.is_empty() {
+        if implied.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        return Ok(vec![ScopeSuggestion {
+            input: commit.source.clone(),
+            span: commit.scope_span().into(),
+            help: format!("The changed files imply the scopes: {implied:?}"),
+        }
+        .into()]);
+    }
+
+    let mut reports = Vec::new();
+
+    for scope in &commit.scope {
+        let Some(set) = sets.get(scope) else {
+            continue;
+        };
+
+        if files.iter().any(|file| set.is_match(file)) {
+            continue;
+        }
+
+        let help = if implied.is_empty() {
+            format!(
+                "None of the changed files are in the paths of `{scope}`: {:?}",
+                config.commit_scope.paths[*scope]
+            )
+        } else {
+            format!("The changed files imply the scopes: {implied:?}")
+        };
+
+        reports.push(
+            LintError {
+                input: commit.source.clone(),
+                span: commit.scope_span().into(),
+                label: None,
+                help: Some(help.leak()),
+                kind: LintErrorKind::ScopePathsMismatch,
+            }
+            .into(),
+        );
+    }
+
+    Ok(reports)
+}
+
+fn glob_set(scope: &str, globs: &[String]) -> miette::Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+
+    for glob in globs {
+        builder.add(
+            path_glob(glob)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("Invalid glob in `scope.paths.{scope}`"))?,
+        );
+    }
+
+    builder.build().into_diagnostic()
+}
+
+/// Parses a glob of `scope.paths`, in which `*` does not match `/`.
+pub(crate) fn path_glob(glob: &str) -> Result<Glob, globset::Error> {
+    GlobBuilder::new(glob).literal_separator(true).build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_commit() -> Commit<'static> {
+        Commit {
+            commit_type: "fix",
+            scope: vec!["ui"],
+            breaking_change: false,
+            subject: "subject",
+            prefix: None,
+            suffix: None,
+            body: None,
+            footer: Default::default(),
+            source: "fix(ui): subject".to_string(),
+        }
+    }
+
+    fn config() -> Conf {
+        let mut config = Conf::default();
+        config.commit_scope.paths = [
+            ("ui".to_string(), vec!["frontend/**".to_string()]),
+            ("core".to_string(), vec!["crates/core/**".to_string()]),
+        ]
+        .into();
+
+        config
+    }
+
+    fn files(files: &[&str]) -> Vec<String> {
+        files.iter().map(|file| file.to_string()).collect()
+    }
+
+    #[test]
+    fn test_matching_scope() {
+        let reports = check_paths(
+            &sample_commit(),
+            &config(),
+            &files(&["frontend/src/app.ts", "README.md"]),
+        )
+        .unwrap();
+
+        assert!(reports.is_empty());
+    }
+
+    #[test]
+    fn test_mismatched_scope() {
+        let reports = check_paths(
+            &sample_commit(),
+            &config(),
+            &files(&["crates/core/src/lib.rs"]),
+        )
+        .unwrap();
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(
+            reports[0].help().unwrap().to_string(),
+            "The changed files imply the scopes: [\"core\"]"
+        );
+    }
+
+    #[test]
+    fn test_scope_without_paths() {
+        let mut commit = sample_commit();
+        commit.scope = vec!["docs"];
+
+        let reports = check_paths(&commit, &config(), &files(&["README.md"])).unwrap();
+
+        assert!(reports.is_empty());
+    }
+
+    #[test]
+    fn test_suggestion() {
+        let mut commit = sample_commit();
+        commit.scope = vec![];
+
+        let reports = check_paths(&commit, &config(), &files(&["frontend/index.html"])).unwrap();
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].severity(), Some(miette::Severity::Warning));
+    }
+
+    #[test]
+    fn test_literal_separator() {
+        let mut config = config();
+        config.commit_scope.paths = [("ui".to_string(), vec!["frontend/*".to_string()])].into();
+
+        let reports =
+            check_paths(&sample_commit(), &config, &files(&["frontend/src/app.ts"])).unwrap();
+
+        assert_eq!(reports.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_glob() {
+        let mut config = config();
+        config.commit_scope.paths = [("ui".to_string(), vec!["frontend/[".to_string()])].into();
+
+        assert!(check_paths(&sample_commit(), &config, &files(&["README.md"])).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::BTreeMap;
+
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::pattern::{Pattern, Patterns};
+
+/// The allowed children of a segment in a hierarchical scope.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub(crate) enum ScopeTree {
+    /// Segments that have no children of their own.
+    Leaves(Patterns),
+    /// Segments with their own children.
+    Branches(Branches),
+}
+
+/// Segments with their children, written as a table. Each segment is compiled to a [`Pattern`]
+/// once, when the configuration is loaded.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Branches(Vec<(Segment, ScopeTree)>);
+
+impl Branches {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The segments as written, with their children.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&String, &ScopeTree)> {
+        self.0
+            .iter()
+            .map(|(segment, child)| (&segment.entry, child))
+    }
+}
+
+impl Serialize for Branches {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Branches {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let branches = BTreeMap::<String, ScopeTree>::deserialize(deserializer)?;
+
+        Ok(Self(
+            branches
+                .into_iter()
+                .map(|(entry, child)| (Segment::from(entry), child))
+                .collect(),
+        ))
+    }
+}
+
+impl JsonSchema for Branches {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        BTreeMap::<String, ScopeTree>::schema_name()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        BTreeMap::<String, ScopeTree>::json_schema(gen)
+    }
+}
+
+/// A segment with children, and its compiled entry.
+#[derive(Debug, Clone)]
+struct Segment {
+    entry: String,
+    /// Left out if the entry is an invalid pattern, which is reported when the configuration is
+    /// validated.
+    pattern: Option<Pattern>,
+}
+
+impl Segment {
+    fn matches(&self, segment: &str) -> bool {
+        self.pattern
+            .as_ref()
+            .is_some_and(|pattern| pattern.matches(segment))
+    }
+}
+
+impl From<String> for Segment {
+    fn from(entry: String) -> Self {
+        let pattern = Pattern::parse(&entry).ok();
+
+        Self { entry, pattern }
+    }
+}
+
+impl PartialEq for Segment {
+    fn eq(&self, other: &Self) -> bool {
+        self.entry == other.entry
+    }
+}
+
+/// A segment that is not allowed at its position in the tree.
+#[derive(Debug, PartialEq)]
+pub(crate) struct InvalidSegment {
+    /// Index of the segment in the scope.
+    pub(crate) index: usize,
+    /// The segments allowed at its position.
+    pub(crate) allowed: Vec<String>,
+}
+
+/// Walks the segments of a scope down the tree. A scope may stop at any level of the tree, but
+/// each of its segments must be a child of the previous one.
+pub(crate) fn check_segments(tree: &Branches, segments: &[&str]) -> Result<(), InvalidSegment> {
+    let mut level = Some(tree);
+    let mut leaves: Option<&Patterns> = None;
+
+    for (index, segment) in segments.iter().enumerate() {
+        let invalid = |allowed: Vec<&String>| InvalidSegment {
+            index,
+            allowed: allowed.into_iter().cloned().collect(),
+        };
+
+        if let Some(allowed) = leaves.take() {
+            if !allowed.matches(segment) {
+                return Err(invalid(allowed.iter().collect()));
+            }
+
+            continue;
+        }
+
+        let Some(branches) = level.take() else {
+            return Err(invalid(Vec::new()));
+        };
+
+        match branches.0.iter().find(|(key, _)| key.matches(segment)) {
+            Some((_, ScopeTree::Branches(children))) => level = Some(children),
+            Some((_, ScopeTree::Leaves(children))) => leaves = Some(children),
+            None => return Err(invalid(branches.iter().map(|(key, _)| key).collect())),
+        }
+    }
+
+    Ok(())
+}
+
+impl ScopeTree {
+    /// All segments in the tree, at any level.
+    pub(crate) fn values(&self) -> Vec<&String> {
+        match self {
+            ScopeTree::Leaves(leaves) => leaves.iter().collect(),
+            ScopeTree::Branches(branches) => branches
+                .iter()
+                .flat_map(|(key, child)| std::iter::once(key).chain(child.values()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> Branches {
+        toml::from_str(
+            "api = [\"auth\", \"billing\"]\nci = []\n\n[ui]\nsettings = [\"profile\"]\ntheme = []\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_valid() {
+        assert_eq!(check_segments(&tree(), &["api", "auth"]), Ok(()));
+        assert_eq!(
+            check_segments(&tree(), &["ui", "settings", "profile"]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_prefix() {
+        assert_eq!(check_segments(&tree(), &["ui"]), Ok(()));
+        assert_eq!(check_segments(&tree(), &["ui", "settings"]), Ok(()));
+    }
+
+    #[test]
+    fn test_invalid_root() {
+        assert_eq!(
+            check_segments(&tree(), &["docs"]),
+            Err(InvalidSegment {
+                index: 0,
+                allowed: vec!["api".to_string(), "ci".to_string(), "ui".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_child() {
+        assert_eq!(
+            check_segments(&tree(), &["api", "users"]),
+            Err(InvalidSegment {
+                index: 1,
+                allowed: vec!["auth".to_string(), "billing".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_too_deep() {
+        assert_eq!(
+            check_segments(&tree(), &["api", "auth", "token"]),
+            Err(InvalidSegment {
+                index: 2,
+                allowed: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn test_pattern_segment() {
+        let tree: Branches = toml::from_str("[\"^ui-.+\"]\nsettings = []\n").unwrap();
+
+        assert_eq!(check_segments(&tree, &["ui-admin", "settings"]), Ok(()));
+        assert!(check_segments(&tree, &["ui"]).is_err());
+    }
+
+    #[test]
+    fn test_serialize() {
+        let tree = tree();
+
+        assert_eq!(
+            toml::from_str::<Branches>(&toml::to_string(&tree).unwrap()).unwrap(),
+            tree
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use std::fmt;
+
+use serde::{
+    de::{DeserializeSeed, Error, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+
+use super::{BodyConf, BreakingConf, Conf, FooterConf, ScopeConf, Scopes, SubjectConf};
+
+/// Rules that apply to commits of a single type, taking precedence over the global rules. Only the
+/// keys that are set are overridden.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(transparent)]
+pub(crate) struct TypeOverrides(toml::Table);
+
+/// The keys that can be overridden per type. Only used to validate overrides, and to generate
+/// their schema.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[allow(dead_code)]
+struct TypeOverridesSchema {
+    scope: Option<ScopeConf>,
+    subject: Option<SubjectConf>,
+    body: Option<BodyConf>,
+    footer: Option<FooterConf>,
+    breaking: Option<BreakingConf>,
+}
+
+/// The sections of [`TypeOverridesSchema`].
+const SECTIONS: &[&str] = &["scope", "subject", "body", "footer", "breaking"];
+
+/// Reads the overrides one key at a time, and checks each value against [`TypeOverridesSchema`]
+/// while it is read, so errors point at the value instead of the whole table.
+impl<'de> Deserialize<'de> for TypeOverrides {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_map(TableVisitor { section: None })
+            .map(Self)
+    }
+}
+
+/// Visits the overrides, or one of their sections.
+struct TableVisitor<'a> {
+    section: Option<&'a str>,
+}
+
+impl<'de> Visitor<'de> for TableVisitor<'_> {
+    type Value = toml::Table;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a table")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut table = toml::Table::new();
+
+        while let Some(key) = map.next_key::<String>()? {
+            let value = match self.section {
+                None => toml::Value::Table(map.next_value_seed(Section(&key))?),
+                Some(section) => map.next_value_seed(Key { section, key: &key })?,
+            };
+
+            table.insert(key, value);
+        }
+
+        Ok(table)
+    }
+}
+
+/// A section of the overrides, e.g. `scope`.
+struct Section<'a>(&'a str);
+
+impl<'de> DeserializeSeed<'de> for Section<'_> {
+    type Value = toml::Table;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        if !SECTIONS.contains(&self.0) {
+            return Err(D::Error::unknown_field(self.0, SECTIONS));
+        }
+
+        deserializer.deserialize_map(TableVisitor {
+            section: Some(self.0),
+        })
+    }
+}
+
+/// A key of a section, e.g. `required` in `scope`.
+struct Key<'a> {
+    section: &'a str,
+    key: &'a str,
+}
+
+impl<'de> DeserializeSeed<'de> for Key<'_> {
+    type Value = toml::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let value = toml::Value::deserialize(deserializer)?;
+
+        let key = toml::Table::from_iter([(self.key.to_string(), value.clone())]);
+        let section = toml::Table::from_iter([(self.section.to_string(), key.into())]);
+
+        toml::Value::Table(section)
+            .try_into::<TypeOverridesSchema>()
+            .map_err(|error| D::Error::custom(error.message()))?;
+
+        Ok(value)
+    }
+}
+
+impl JsonSchema for TypeOverrides {
+    fn schema_name() -> String {
+        "TypeOverrides".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        TypeOverridesSchema::json_schema(gen)
+    }
+}
+
+impl TypeOverrides {
+    /// Whether the override sets the key, given as the path of its nested keys.
+    pub(crate) fn contains(&self, key: &[&str]) -> bool {
+        let Some((last, path)) = key.split_last() else {
+            return false;
+        };
+
+        path.iter()
+            .try_fold(&self.0, |table, key| table.get(*key)?.as_table())
+            .is_some_and(|table| table.contains_key(*last))
+    }
+
+    /// Applies `f` to the overridden scope enum, if there is one.
+    pub(crate) fn map_scopes(&mut self, f: impl FnOnce(&mut Scopes)) {
+        let Some(value) = self
+            .0
+            .get_mut("scope")
+            .and_then(|scope| scope.get_mut("enum"))
+        else {
+            return;
+        };
+
+        let mut scopes = value
+            .clone()
+            .try_into::<Scopes>()
+            .expect("overrides are validated when they are loaded");
+
+        f(&mut scopes);
+
+        *value = toml::Value::try_from(scopes).expect("scopes serialize to a value");
+    }
+}
+
+impl Conf {
+    /// Whether `[types.<commit_type>]` overrides the key, given as the path of its nested keys.
+    pub(crate) fn is_overridden(&self, commit_type: &str, key: &[&str]) -> bool {
+        self.types
+            .get(commit_type)
+            .is_some_and(|overrides| overrides.contains(key))
+    }
+
+    /// The configuration for commits of the type, with its overrides applied.
+    pub(crate) fn for_type(&self, commit_type: &str) -> Conf {
+        let Some(overrides) = self.types.get(commit_type) else {
+            return self.clone();
+        };
+
+        let toml::Value::Table(mut table) =
+            toml::Value::try_from(self).expect("the configuration serializes to a table")
+        else {
+            unreachable!("the configuration serializes to a table");
+        };
+
+        merge(&mut table, &overrides.0);
+
+        toml::Value::Table(table)
+            .try_into()
+            .expect("overrides are validated when they are loaded")
+    }
+}
+
+/// Deep merges `overrides` into `table`.
+fn merge(table: &mut toml::Table, overrides: &toml::Table) {
+    for (key, value) in overrides {
+        match (table.get_mut(key), value) {
+            (Some(toml::Value::Table(nested)), toml::Value::Table(value)) => merge(nested, value),
+            _ => {
+                table.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(input: &str) -> Result<Conf, toml::de::Error> {
+        toml::from_str(input)
+    }
+
+    #[test]
+    fn test_for_type() {
+        let config = config(
+            "[scope]\nmin-length = 2\n\n[types.feat]\nscope.required = true\nfooter.required = [\"Refs\"]\n",
+        )
+        .unwrap();
+
+        let feat = config.for_type("feat");
+        assert!(feat.commit_scope.required);
+        assert_eq!(feat.commit_scope.min_length, 2);
+        assert_eq!(feat.commit_footer.required, vec!["Refs".to_string()]);
+
+        let chore = config.for_type("chore");
+        assert!(!chore.commit_scope.required);
+        assert!(chore.commit_footer.required.is_empty());
+    }
+
+    #[test]
+    fn test_is_overridden() {
+        let config = config("[types.docs]\nbreaking.allowed = false\n").unwrap();
+
+        assert!(config.is_overridden("docs", &["breaking", "allowed"]));
+        assert!(!config.is_overridden("docs", &["breaking", "require-footer"]));
+        assert!(!config.is_overridden("docs", &["body", "required"]));
+        assert!(!config.is_overridden("feat", &["breaking", "allowed"]));
+    }
+
+    #[test]
+    fn test_unknown_key() {
+        let error = config("[types.feat]\nscope.requird = true\n").unwrap_err();
+
+        assert!(error.message().contains("unknown field `requird`"));
+    }
+
+    #[test]
+    fn test_invalid_value_span() {
+        let input = "[types.feat]\nbody.required = true\nscope.min-length = \"two\"\n";
+        let error = config(input).unwrap_err();
+
+        assert!(error.message().contains("expected usize"));
+        assert_eq!(error.span(), Some(53..58));
+    }
+
+    #[test]
+    fn test_type_rules_cannot_be_overridden() {
+        let error = config("[types.feat]\ntype.min-length = 2\n").unwrap_err();
+
+        assert!(error.message().contains("unknown field `type`"));
+    }
+}
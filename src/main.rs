@@ -1,10 +1,14 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
 use clap_stdin::MaybeStdin;
-use linting::{lint, Conf};
-use miette::Context;
+use config::{extract, figment, import_commitlint, init, load_config, schema, show, ConfigArgs};
+use linting::{fix, lint, lint_range, Conf};
+use miette::{Context, IntoDiagnostic};
 
+mod compose;
+mod config;
+mod git;
 mod linting;
 mod parsing;
 
@@ -14,9 +18,8 @@ mod parsing;
 struct Args {
     #[clap(subcommand)]
     command: Command,
-    #[clap(short, long)]
-    /// Path to the configuration file. Overrides the default configuration.
-    config: Option<PathBuf>,
+    #[clap(flatten)]
+    config: ConfigArgs,
 }
 
 #[derive(Debug, Subcommand)]
@@ -24,7 +27,33 @@ enum Command {
     /// Lint a commit message.
     Lint {
         /// Message to lint
-        message: MaybeStdin<String>,
+        #[clap(required_unless_present_any = ["file", "range"])]
+        message: Option<MaybeStdin<String>>,
+        #[clap(short, long, conflicts_with = "message")]
+        /// Read the message to lint from a file, e.g. `.git/COMMIT_EDITMSG`.
+        file: Option<PathBuf>,
+        #[clap(long)]
+        /// Apply safe fixes to the message. The fixed message is written back to the file given
+        /// with `--file`, or printed to stdout otherwise.
+        fix: bool,
+        #[clap(long)]
+        /// Check the scope against the staged files, using `scope.paths`. Meant for the
+        /// `commit-msg` hook.
+        staged: bool,
+        #[clap(long, conflicts_with_all = ["message", "file", "fix", "staged"])]
+        /// Lint every commit in a revision range, e.g. `main..HEAD`, checking each scope against
+        /// the files the commit changes. Merge commits are skipped.
+        range: Option<String>,
+        #[clap(long)]
+        /// Branch to check breaking changes against, using `breaking.forbidden-branches`.
+        /// Defaults to the checked out branch, or with `--range`, the branch the range ends at.
+        branch: Option<String>,
+    },
+    /// Interactively compose a commit message, and commit it.
+    Commit {
+        #[clap(long)]
+        /// Print the message to stdout instead of running `git commit`.
+        print: bool,
     },
     /// Commands related to configuration.
     Config {
@@ -37,20 +66,154 @@ enum Command {
 enum ConfigCommand {
     /// Print the default configuration to stdout.
     Dump,
+    /// Check the configuration for unknown keys and invalid values.
+    Validate,
+    /// Print the effective configuration, annotated with where each value came from.
+    Show,
+    /// Translate a commitlint configuration file to change-scribe configuration, and print it to
+    /// stdout.
+    ImportCommitlint {
+        /// Path to the commitlint configuration, e.g. `.commitlintrc.json`.
+        file: PathBuf,
+    },
+    /// Print the JSON Schema of the configuration file to stdout.
+    Schema,
+    /// Write a starter `.change-scribe.toml`, based on the types and scopes used in the commit
+    /// history.
+    Init {
+        #[clap(long)]
+        /// Print the configuration to stdout instead of writing it.
+        print: bool,
+        #[clap(long)]
+        /// Overwrite an existing `.change-scribe.toml`.
+        force: bool,
+    },
 }
 
 fn main() -> Result<(), miette::Report> {
     let args = Args::parse();
 
     match args.command {
-        Command::Lint { message } => {
-            lint(Box::new(message.into_inner()).leak(), args.config)?;
+        Command::Lint {
+            message,
+            file,
+            fix: apply_fixes,
+            staged,
+            range,
+            branch,
+        } => {
+            let config = load_config(&args.config)?;
+
+            if let Some(range) = range {
+                let branch = branch.or_else(|| git::range_branch(&range));
+                return lint_range(&range, &config, branch.as_deref());
+            }
+
+            let branch = branch.or_else(|| git::branch("HEAD"));
+
+            let message = match (message, &file) {
+                (Some(message), _) => message.into_inner(),
+                (None, Some(file)) => std::fs::read_to_string(file)
+                    .into_diagnostic()
+                    .wrap_err_with(|| format!("Failed to read `{}`", file.display()))?,
+                (None, None) => unreachable!("clap requires a message, a file or a range"),
+            };
+
+            let message = if apply_fixes {
+                let fixed = fix(Box::new(message).leak(), &config)?;
+
+                match &file {
+                    Some(file) => std::fs::write(file, &fixed)
+                        .into_diagnostic()
+                        .wrap_err_with(|| format!("Failed to write `{}`", file.display()))?,
+                    None => print!("{fixed}"),
+                }
+
+                fixed
+            } else {
+                message
+            };
+
+            let files = if staged {
+                Some(git::staged_files()?)
+            } else {
+                None
+            };
+
+            lint(
+                Box::new(message).leak(),
+                &config,
+                files.as_deref(),
+                branch.as_deref(),
+            )?;
+        }
+        Command::Commit { print } => {
+            let config = load_config(&args.config)?;
+
+            let message = compose::compose(&config)?;
+            lint(
+                message.clone().leak(),
+                &config,
+                None,
+                git::branch("HEAD").as_deref(),
+            )?;
+
+            if print {
+                println!("{message}");
+            } else {
+                compose::commit(&message)?;
+            }
         }
         Command::Config { command } => match command {
             ConfigCommand::Dump => {
                 let config = default_config()?;
                 println!("{}", config);
             }
+            ConfigCommand::Validate => {
+                load_config(&args.config)?;
+                println!("The configuration is valid");
+            }
+            ConfigCommand::Show => {
+                let figment = figment(&args.config)?;
+                let config = extract(&figment)?;
+
+                print!("{}", show(&config, &figment)?);
+            }
+            ConfigCommand::ImportCommitlint { file } => {
+                let import = import_commitlint(&file)?;
+
+                for unmapped in &import.unmapped {
+                    eprintln!("Could not import {unmapped}");
+                }
+
+                print!("{}", import.config);
+            }
+            ConfigCommand::Schema => {
+                println!("{}", schema()?);
+            }
+            ConfigCommand::Init { print, force } => {
+                let config = init()?;
+
+                if print {
+                    print!("{config}");
+                } else {
+                    let path = PathBuf::from(".change-scribe.toml");
+
+                    if path.exists() && !force {
+                        miette::bail!(
+                            help = "Pass `--force` to overwrite it",
+                            "`{}` already exists",
+                            path.display()
+                        );
+                    }
+
+                    std::fs::write(&path, config)
+                        .into_diagnostic()
+                        .wrap_err_with(|| format!("Failed to write `{}`", path.display()))?;
+
+                    println!("Wrote `{}`", path.display());
+                }
+            }
         },
     }
 
@@ -67,27 +230,95 @@ fn default_config() -> miette::Result<String> {
     Ok(config)
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[allow(dead_code)]
 struct Commit<'a> {
     commit_type: &'a str,
     scope: Vec<&'a str>,
     breaking_change: bool,
     subject: &'a str,
+    /// Text before the type, e.g. a ticket, when the header grammar has a prefix.
+    prefix: Option<&'a str>,
+    /// Text after the subject, e.g. a pull request number, when the header grammar has a suffix.
+    suffix: Option<&'a str>,
     body: Option<&'a str>,
-    footer: HashMap<&'a str, &'a str>,
+    /// The footers in the order they appear in, so keys like `Signed-off-by` can repeat.
+    footer: Vec<Footer<'a>>,
     source: String,
 }
 
-impl Commit<'_> {
+/// A footer of the message, e.g. `Refs: #123`.
+#[derive(Debug, Clone, PartialEq)]
+struct Footer<'a> {
+    key: &'a str,
+    value: &'a str,
+    /// The offset of the key in the source.
+    offset: usize,
+}
+
+impl<'a> Commit<'a> {
+    /// The value of the first footer with the key.
+    fn footer_value(&self, key: &str) -> Option<&'a str> {
+        self.footer
+            .iter()
+            .find(|footer| footer.key == key)
+            .map(|footer| footer.value)
+    }
+
     fn type_span(&self) -> (usize, usize) {
-        (0, self.commit_type.len())
+        let start = self.prefix.map_or(0, |prefix| prefix.len() + 1);
+
+        (start, self.commit_type.len())
     }
 
     fn scope_span(&self) -> (usize, usize) {
-        let start = self.commit_type.len() + 1;
+        let start = self.type_span().0 + self.commit_type.len() + 1;
         let end = self.scope.join(":").len();
 
         (start, end)
     }
+
+    fn subject_span(&self) -> (usize, usize) {
+        let end = match self.suffix {
+            Some(suffix) => self.source[..self.header_len()].trim_end().len() - suffix.len() - 1,
+            None => self.header_len(),
+        };
+
+        (end - self.subject.len(), self.subject.len())
+    }
+
+    fn prefix_span(&self) -> (usize, usize) {
+        (0, self.prefix.map_or(0, str::len))
+    }
+
+    fn suffix_span(&self) -> (usize, usize) {
+        let end = self.source[..self.header_len()].trim_end().len();
+        let len = self.suffix.map_or(0, str::len);
+
+        (end - len, len)
+    }
+
+    /// The `!` in the header, or the breaking change footer when the header has none.
+    fn breaking_span(&self) -> (usize, usize) {
+        let (start, len) = if self.scope.is_empty() {
+            self.type_span()
+        } else {
+            let (start, len) = self.scope_span();
+            (start, len + 1)
+        };
+        let end = start + len;
+
+        if self.source[end..].starts_with('!') {
+            return (end, 1);
+        }
+
+        self.footer
+            .iter()
+            .find(|footer| parsing::BREAKING_CHANGE_TOKENS.contains(&footer.key))
+            .map_or((end, 0), |footer| (footer.offset, footer.key.len()))
+    }
+
+    fn header_len(&self) -> usize {
+        self.source.find("\n\n").unwrap_or(self.source.len())
+    }
 }
@@ -1,18 +1,88 @@
-use std::collections::HashMap;
-
 use miette::{Diagnostic, SourceSpan};
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_until, take_while1},
-    character::complete::anychar,
-    combinator::{eof, map, peek},
-    multi::many_till,
+    bytes::complete::{tag, tag_no_case, take_until, take_while1},
+    combinator::{map, not, opt},
+    error::ErrorKind,
     sequence::tuple,
     Err, IResult,
 };
+use regex::Regex;
 use thiserror::Error;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{Commit, Footer};
+
+/// Optional parts around the conventional header, e.g. a ticket before the type or a pull
+/// request number after the subject.
+#[derive(Debug)]
+pub(crate) struct Grammar {
+    /// Matches the prefix, followed by a space, at the start of the header.
+    prefix: Option<Regex>,
+    /// Matches the suffix, preceded by a space, at the end of the header.
+    suffix: Option<Regex>,
+    /// Separators between the key and the value of a footer.
+    separators: Vec<String>,
+}
+
+impl Default for Grammar {
+    fn default() -> Self {
+        Self {
+            prefix: None,
+            suffix: None,
+            separators: DEFAULT_SEPARATORS.map(str::to_string).to_vec(),
+        }
+    }
+}
 
-use crate::Commit;
+impl Grammar {
+    /// Builds the grammar from the patterns of the prefix and the suffix, without the separating
+    /// space.
+    pub(crate) fn new(prefix: Option<&str>, suffix: Option<&str>) -> Result<Self, regex::Error> {
+        Ok(Self {
+            prefix: prefix
+                .map(|prefix| Regex::new(&format!(r"\A(?:{prefix}) ")))
+                .transpose()?,
+            suffix: suffix
+                .map(|suffix| Regex::new(&format!(r" ((?:{suffix}))\s*\z")))
+                .transpose()?,
+            ..Default::default()
+        })
+    }
+
+    /// Replaces the footer separators. Empty separators are ignored.
+    pub(crate) fn with_separators(mut self, separators: &[String]) -> Self {
+        self.separators = separators
+            .iter()
+            .filter(|separator| !separator.is_empty())
+            .cloned()
+            .collect();
+        self
+    }
+
+    /// Splits the prefix off the start of the header.
+    fn split_prefix<'a>(&self, input: &'a str) -> (Option<&'a str>, &'a str) {
+        match self.prefix.as_ref().and_then(|prefix| prefix.find(input)) {
+            Some(found) => (Some(&input[..found.end() - 1]), &input[found.end()..]),
+            None => (None, input),
+        }
+    }
+
+    /// Splits the suffix off the end of the subject.
+    fn split_suffix<'a>(&self, subject: &'a str) -> (&'a str, Option<&'a str>) {
+        let Some(captures) = self
+            .suffix
+            .as_ref()
+            .and_then(|suffix| suffix.captures(subject))
+        else {
+            return (subject, None);
+        };
+
+        let suffix = captures.get(1).expect("the suffix is captured");
+
+        (&subject[..suffix.start() - 1], Some(suffix.as_str()))
+    }
+}
 
 #[derive(Debug, Diagnostic, Error)]
 #[error("{kind}")]
@@ -29,31 +99,94 @@ struct ParseError {
 
 #[derive(Debug, Error)]
 enum ParseErrorKind {
-    #[error("Invalid commit type syntax")]
-    ParsingType,
+    #[error("Missing commit type")]
+    MissingType,
+    #[error("Invalid character in commit type")]
+    InvalidType,
+    #[error("Unclosed scope")]
+    UnclosedScope,
+    #[error("Missing `: ` after the commit type")]
+    MissingSeparator,
+    #[error("Missing space after the `:`")]
+    MissingSeparatorSpace,
+    #[error("Missing description")]
+    MissingDescription,
 }
 
-pub(crate) fn parse(message: &'static str) -> Result<Commit, miette::Report> {
-    let result = parse_internal(message).map_err(|e| {
-        let input = match e {
-            Err::Error(e) | Err::Failure(e) => e.input,
-            Err::Incomplete(_) => message,
-        };
-
-        ParseError {
-            input: message.to_string(),
-            span: (message.len() - input.len(), 0).into(),
-            label: None,
-            help: None,
-            kind: ParseErrorKind::ParsingType,
+impl ParseErrorKind {
+    /// Tells why the separator could not be parsed, from the text where it was expected, and
+    /// whether that text directly follows the commit type.
+    fn at_separator(input: &str, after_type: bool) -> Self {
+        match input.chars().next() {
+            Some('(') => Self::UnclosedScope,
+            Some(':') => Self::MissingSeparatorSpace,
+            Some(c) if after_type && !c.is_whitespace() => Self::InvalidType,
+            _ => Self::MissingSeparator,
         }
+    }
+}
+
+pub(crate) fn parse(message: &'static str) -> Result<Commit<'static>, miette::Report> {
+    parse_with(message, &Grammar::default())
+}
+
+/// Parses the message, recognizing the prefix and suffix of the grammar.
+pub(crate) fn parse_with(
+    message: &'static str,
+    grammar: &Grammar,
+) -> Result<Commit<'static>, miette::Report> {
+    let commit = parse_internal(message, grammar).map_err(|(input, kind)| ParseError {
+        input: message.to_string(),
+        span: (message.len() - input.len(), 0).into(),
+        label: None,
+        help: None,
+        kind,
     })?;
 
-    Ok(result.1)
+    Ok(commit)
+}
+
+/// Returns the offset directly after the `:` of the header when it is not followed by a space.
+pub(crate) fn missing_separator_space(message: &str, grammar: &Grammar) -> Option<usize> {
+    let (_, header) = grammar.split_prefix(message);
+
+    let (rest, _) = tuple((
+        parse_type,
+        opt(parse_scope),
+        opt(parse_exclaimation_mark),
+        tag(":"),
+        not(tag(" ")),
+    ))(header)
+    .ok()?;
+
+    Some(message.len() - rest.len())
 }
 
-fn parse_internal(message: &'static str) -> IResult<&str, Commit> {
-    let (rest, commit_type) = parse_type(message)?;
+/// The footer tokens that mark a breaking change, which are synonyms of each other.
+pub(crate) const BREAKING_CHANGE_TOKENS: [&str; 2] = ["BREAKING CHANGE", "BREAKING-CHANGE"];
+
+/// The separators of the Conventional Commits specification, e.g. `Refs: #123` and `Refs #123`.
+pub(crate) const DEFAULT_SEPARATORS: [&str; 2] = [": ", " #"];
+
+/// Lines that git itself adds to the trailer block, which let it contain other lines as well.
+const GIT_GENERATED_PREFIXES: [&str; 2] = ["Signed-off-by: ", "(cherry picked from commit "];
+
+/// Checks whether the input is a single footer, e.g. `Refs: #123`.
+pub(crate) fn is_footer(input: &str, grammar: &Grammar) -> bool {
+    !input.contains('\n')
+        && parse_trailer(input, grammar).is_some_and(|(_, value)| !value.is_empty())
+}
+
+/// Parses the message, or returns the text where parsing failed and why.
+fn parse_internal(
+    message: &'static str,
+    grammar: &Grammar,
+) -> Result<Commit<'static>, (&'static str, ParseErrorKind)> {
+    let (prefix, rest) = grammar.split_prefix(message);
+
+    let (after_type, commit_type) =
+        parse_type(rest).map_err(|_| (rest, ParseErrorKind::MissingType))?;
+    let rest = after_type;
 
     let (rest, commit_scope) = match parse_scope(rest) {
         Ok((rest, commit_scope)) => (rest, Some(commit_scope)),
@@ -65,9 +198,16 @@ fn parse_internal(message: &'static str) -> IResult<&str, Commit> {
         Err(_) => (rest, false),
     };
 
-    let (rest, _) = parse_seperator(rest)?;
+    let (rest, _) = parse_seperator(rest).map_err(|_| {
+        let kind = ParseErrorKind::at_separator(rest, rest.len() == after_type.len());
+        (rest, kind)
+    })?;
 
-    let (rest, commit_subject) = parse_subject(rest)?;
+    let (rest, commit_subject) = parse_subject(rest)
+        .ok()
+        .filter(|(_, subject)| !subject.trim().is_empty())
+        .ok_or((rest, ParseErrorKind::MissingDescription))?;
+    let (commit_subject, suffix) = grammar.split_suffix(commit_subject);
 
     if rest.is_empty() {
         let commit = Commit {
@@ -77,38 +217,21 @@ fn parse_internal(message: &'static str) -> IResult<&str, Commit> {
                 .unwrap_or_default(),
             breaking_change,
             subject: commit_subject,
+            prefix,
+            suffix,
             body: None,
-            footer: HashMap::new(),
+            footer: Vec::new(),
             source: message.to_string(),
         };
 
-        return Ok((rest, commit));
-    }
-
-    let mut rest = rest;
-    let mut commit_body = String::new();
-
-    while !rest.is_empty() {
-        let (new_rest, _) = parse_section_seperator(rest)?;
-        rest = new_rest;
-
-        if parse_footer_key(rest).is_ok() {
-            break;
-        } else {
-            let (new_rest, parsed) = parse_body(rest)?;
-            rest = new_rest;
-            commit_body.push_str(parsed);
-        }
+        return Ok(commit);
     }
 
-    let mut footer = HashMap::<&str, &str>::new();
+    let (body, mut footer) = split_footer(rest, grammar);
 
-    while !rest.is_empty() {
-        let (new_rest, key) = parse_footer_key(rest)?;
-        let (new_rest, value) = parse_footer_value(new_rest)?;
-        footer.insert(key, value.leak());
-
-        rest = new_rest;
+    // The footers are split from the text after the header, so their offsets are relative to it
+    for footer in &mut footer {
+        footer.offset += message.len() - rest.len();
     }
 
     let commit = Commit {
@@ -116,18 +239,37 @@ fn parse_internal(message: &'static str) -> IResult<&str, Commit> {
         scope: commit_scope
             .map(|scope| scope.split(',').collect())
             .unwrap_or_default(),
-        breaking_change: breaking_change || footer.contains_key("BREAKING CHANGE"),
+        breaking_change: breaking_change
+            || footer
+                .iter()
+                .any(|footer| BREAKING_CHANGE_TOKENS.contains(&footer.key)),
         subject: commit_subject,
-        body: (!commit_body.is_empty()).then_some(commit_body.leak()),
+        prefix,
+        suffix,
+        body,
         footer,
         source: message.to_string(),
     };
 
-    Ok((rest, commit))
+    Ok(commit)
 }
 
+/// Takes letters and `-`, a whole grapheme at a time, so combining marks stay with their letter.
 fn parse_type(input: &str) -> IResult<&str, &str> {
-    take_while1(|c: char| c.is_alphabetic() || c == '-')(input)
+    let len = input
+        .graphemes(true)
+        .take_while(|grapheme| grapheme.starts_with(|c: char| c.is_alphabetic() || c == '-'))
+        .map(str::len)
+        .sum::<usize>();
+
+    if len == 0 {
+        return Err(Err::Error(nom::error::Error::new(
+            input,
+            ErrorKind::TakeWhile1,
+        )));
+    }
+
+    Ok((&input[len..], &input[..len]))
 }
 
 fn parse_scope(input: &str) -> IResult<&str, &str> {
@@ -149,36 +291,189 @@ fn parse_subject(input: &str) -> IResult<&str, &str> {
     alt((take_until("\n\n"), take_while1(|_| true)))(input)
 }
 
-fn parse_section_seperator(input: &str) -> IResult<&str, &str> {
-    tag("\n\n")(input)
+/// Splits the text after the header into the body and the footers, following the rules of
+/// `git interpret-trailers`: the footers are the last paragraph, when it consists of trailers and
+/// their indented continuation lines. Git also accepts a block that is at least 25% trailers, when
+/// it contains a line that git generates, like `Signed-off-by`.
+///
+/// Like the Conventional Commits specification, the footers also start at an earlier paragraph
+/// that starts with a breaking change, so its description can span multiple paragraphs.
+///
+/// Lines of the trailer block that are neither trailers nor their continuation lines stay in the
+/// body, as git does not add them to a trailer either, so no text is lost.
+fn split_footer(
+    text: &'static str,
+    grammar: &Grammar,
+) -> (Option<&'static str>, Vec<Footer<'static>>) {
+    let paragraphs = paragraphs(text);
+
+    let breaking = paragraphs.iter().position(|(_, paragraph)| {
+        parse_trailer(paragraph, grammar).is_some_and(|(key, _)| is_breaking_change_token(key))
+    });
+    let block = paragraphs
+        .last()
+        .filter(|(_, paragraph)| is_trailer_block(paragraph, grammar))
+        .map(|_| paragraphs.len() - 1);
+
+    let start = match (breaking, block) {
+        (Some(breaking), Some(block)) => breaking.min(block),
+        (start, None) | (None, start) => start.unwrap_or(paragraphs.len()),
+    };
+
+    let mut footers = Vec::<(usize, &'static str, String)>::new();
+    let mut others = Vec::<&'static str>::new();
+
+    for (index, (offset, paragraph)) in paragraphs.iter().enumerate().skip(start) {
+        let in_block = block == Some(index);
+        let mut offset = *offset;
+        let mut after_trailer = false;
+
+        for (number, line) in paragraph.split_inclusive('\n').enumerate() {
+            let line_offset = offset;
+            offset += line.len();
+            let line = line.strip_suffix('\n').unwrap_or(line);
+
+            if let Some((key, value)) = parse_trailer(line, grammar) {
+                footers.push((line_offset, key, value.to_string()));
+                after_trailer = true;
+                continue;
+            }
+
+            let continuation = line.starts_with(char::is_whitespace);
+
+            // In the trailer block, only continuation lines belong to the trailer before them
+            if in_block && !(after_trailer && continuation) {
+                others.push(line.trim_end());
+                after_trailer = false;
+                continue;
+            }
+
+            let Some((_, _, value)) = footers.last_mut() else {
+                continue;
+            };
+
+            if continuation {
+                value.push(' ');
+                value.push_str(line.trim());
+            } else {
+                value.push_str(if number == 0 { "\n\n" } else { "\n" });
+                value.push_str(line.trim_end());
+            }
+        }
+    }
+
+    let body = paragraphs[..start]
+        .last()
+        .map(|(offset, paragraph)| &text[paragraphs[0].0..offset + paragraph.len()]);
+
+    let body = match (body, others.join("\n")) {
+        (body, others) if others.is_empty() => body,
+        (None, others) => Some(&*others.leak()),
+        (Some(body), others) => Some(&*format!("{body}\n\n{others}").leak()),
+    };
+
+    let footer = footers
+        .into_iter()
+        .map(|(offset, key, value)| Footer {
+            key,
+            value: value.trim().to_string().leak(),
+            offset,
+        })
+        .collect();
+
+    (body, footer)
 }
 
-fn parse_body(input: &str) -> IResult<&str, &str> {
-    alt((take_until("\n\n"), take_while1(|_| true)))(input)
+/// The paragraphs of the text with their offsets, without the blank lines between them.
+fn paragraphs(text: &str) -> Vec<(usize, &str)> {
+    let mut paragraphs = Vec::new();
+    let mut start = None;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        match (start, line.trim().is_empty()) {
+            (None, false) => start = Some(offset),
+            (Some(begin), true) => {
+                paragraphs.push((begin, text[begin..offset].trim_end()));
+                start = None;
+            }
+            _ => {}
+        }
+
+        offset += line.len();
+    }
+
+    if let Some(begin) = start {
+        paragraphs.push((begin, text[begin..].trim_end()));
+    }
+
+    paragraphs
+}
+
+/// Checks whether the paragraph is a trailer block, like `git interpret-trailers` does.
+fn is_trailer_block(paragraph: &str, grammar: &Grammar) -> bool {
+    let mut trailers = 0;
+    let mut others = 0;
+    let mut recognized_prefix = false;
+    let mut after_trailer = false;
+
+    for line in paragraph.lines() {
+        if line.starts_with(char::is_whitespace) && after_trailer {
+            continue;
+        }
+
+        if GIT_GENERATED_PREFIXES
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+        {
+            recognized_prefix = true;
+        }
+
+        after_trailer = parse_trailer(line, grammar).is_some();
+
+        if after_trailer {
+            trailers += 1;
+        } else {
+            others += 1;
+        }
+    }
+
+    trailers > 0 && (others == 0 || (recognized_prefix && trailers * 3 >= others))
 }
 
+/// Parses a line that starts a footer into its key and the rest of the line.
+fn parse_trailer<'a>(line: &'a str, grammar: &Grammar) -> Option<(&'a str, &'a str)> {
+    let (rest, key) = parse_footer_key(line).ok()?;
+    let separator = grammar
+        .separators
+        .iter()
+        .find(|separator| rest.starts_with(separator.as_str()))?;
+
+    Some((key, rest[separator.len()..].trim()))
+}
+
+/// Parses a footer token. Tokens use `-` instead of spaces, except for `BREAKING CHANGE`, which
+/// is also recognized in other cases so its spelling can be checked.
 fn parse_footer_key(input: &str) -> IResult<&str, &str> {
-    map(
-        tuple((
-            alt((
-                tag("BREAKING CHANGE"),
-                take_while1(|c: char| c.is_alphabetic() || c == '-'),
-            )),
-            alt((tag(": "), tag(" #"))),
-        )),
-        |(key, _)| key,
-    )(input)
+    alt((
+        tag_no_case("BREAKING CHANGE"),
+        take_while1(|c: char| c.is_alphanumeric() || c == '-'),
+    ))(input)
 }
 
-fn parse_footer_value(input: &str) -> IResult<&str, String> {
-    map(
-        many_till(anychar, alt((peek(parse_footer_key), eof))),
-        |(taken, _)| taken.into_iter().collect::<String>().trim_end().to_string(),
-    )(input)
+/// Checks whether the key is a breaking change token, in any case.
+pub(crate) fn is_breaking_change_token(key: &str) -> bool {
+    BREAKING_CHANGE_TOKENS
+        .iter()
+        .any(|token| key.eq_ignore_ascii_case(token))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{fs, path::Path};
+
+    use serde_json::{json, Value};
+
     use super::*;
 
     macro_rules! parse_fixtures {
@@ -203,13 +498,84 @@ mod tests {
         test_scope => "../fixtures/scope.txt"
     }
 
+    /// Parses every `<name>.txt` in `fixtures/conformance`, and compares the result with
+    /// `<name>.json`: the parsed commit, or the error and its offset.
+    #[test]
+    fn conformance() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/conformance");
+
+        let mut messages = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+            .collect::<Vec<_>>();
+        messages.sort();
+
+        assert!(!messages.is_empty());
+
+        let failures = messages
+            .iter()
+            .filter_map(|path| {
+                let message = fs::read_to_string(path).unwrap().leak();
+                let expected = fs::read_to_string(path.with_extension("json")).unwrap();
+                let expected = serde_json::from_str::<Value>(&expected).unwrap();
+
+                let actual = match parse(message) {
+                    Ok(commit) => json!({
+                        "type": commit.commit_type,
+                        "scope": commit.scope,
+                        "breaking": commit.breaking_change,
+                        "subject": commit.subject,
+                        "body": commit.body,
+                        "footers": commit
+                            .footer
+                            .iter()
+                            .map(|footer| [footer.key, footer.value])
+                            .collect::<Vec<_>>(),
+                    }),
+                    Err(report) => {
+                        let error = report.downcast_ref::<ParseError>().unwrap();
+                        json!({ "error": error.kind.to_string(), "offset": error.span.offset() })
+                    }
+                };
+
+                (actual != expected).then(|| {
+                    format!(
+                        "{}:\n  expected: {expected}\n  actual:   {actual}",
+                        path.display()
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn configured_separators_are_used() {
+        let grammar = Grammar::default().with_separators(&["=".to_string()]);
+        let commit = parse_with("fix: x\n\nRefs=#1\nReviewed-by: Z", &grammar).unwrap();
+
+        assert_eq!(commit.footer, vec![]);
+        assert_eq!(commit.body, Some("Refs=#1\nReviewed-by: Z"));
+
+        let commit = parse_with("fix: x\n\nRefs=#1", &grammar).unwrap();
+        assert_eq!(
+            commit.footer,
+            vec![Footer {
+                key: "Refs",
+                value: "#1",
+                offset: 8
+            }]
+        );
+    }
+
     macro_rules! valid_footer_keys {
         ( $( $name:ident => $value:literal ),* ) => {
             $(
                 #[test]
                 fn $name() {
-                    let (rest, _) = parse_footer_key($value).unwrap();
-                    assert_eq!(rest, "");
+                    assert!(parse_trailer($value, &Grammar::default()).is_some());
                 }
             )*
         };
@@ -224,13 +590,10 @@ mod tests {
 
     #[test]
     fn terminate_footer_value_on_time() {
-        let (rest, key) = parse_footer_key("Reviewed-by: some guy\nRefs: #123").unwrap();
-        assert_eq!(key, "Reviewed-by");
-
-        let (rest, value) = parse_footer_value(rest).unwrap();
-        assert_eq!(value, "some guy");
+        let commit = parse("fix: x\n\nReviewed-by: some guy\nRefs: #123").unwrap();
 
-        assert_eq!(rest, "Refs: #123");
+        assert_eq!(commit.footer_value("Reviewed-by"), Some("some guy"));
+        assert_eq!(commit.footer_value("Refs"), Some("#123"));
     }
 
     #[test]
@@ -251,6 +614,159 @@ mod tests {
         assert!(commit.breaking_change);
     }
 
+    #[test]
+    fn missing_separator_space_is_detected() {
+        assert_eq!(
+            missing_separator_space("feat(ui)!:subject", &Grammar::default()),
+            Some(10)
+        );
+    }
+
+    #[test]
+    fn present_separator_space_is_not_reported() {
+        assert_eq!(
+            missing_separator_space("feat(ui)!: subject", &Grammar::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn ticket_prefix_is_parsed() {
+        let grammar = Grammar::new(Some("[A-Z]+-[0-9]+"), None).unwrap();
+        let commit = parse_with("PROJ-123 feat(ui): subject", &grammar).unwrap();
+
+        assert_eq!(commit.prefix, Some("PROJ-123"));
+        assert_eq!(commit.commit_type, "feat");
+        assert_eq!(commit.prefix_span(), (0, 8));
+        assert_eq!(commit.type_span(), (9, 4));
+        assert_eq!(commit.subject_span(), (19, 7));
+    }
+
+    #[test]
+    fn gitmoji_prefix_is_parsed() {
+        let grammar = Grammar::new(
+            Some(r":[a-z0-9_+-]+:|\p{Extended_Pictographic}\x{FE0F}?"),
+            None,
+        )
+        .unwrap();
+
+        let commit = parse_with(":sparkles: feat: subject", &grammar).unwrap();
+        assert_eq!(commit.prefix, Some(":sparkles:"));
+
+        let commit = parse_with("\u{2728} feat: subject", &grammar).unwrap();
+        assert_eq!(commit.prefix, Some("\u{2728}"));
+        assert_eq!(commit.commit_type, "feat");
+    }
+
+    #[test]
+    fn prefix_is_optional() {
+        let grammar = Grammar::new(Some("[A-Z]+-[0-9]+"), None).unwrap();
+        let commit = parse_with("feat: subject", &grammar).unwrap();
+
+        assert_eq!(commit.prefix, None);
+        assert_eq!(commit.commit_type, "feat");
+    }
+
+    #[test]
+    fn suffix_is_parsed() {
+        let grammar = Grammar::new(None, Some(r"\(#[0-9]+\)")).unwrap();
+        let commit = parse_with("fix: subject (#42)\n\nbody", &grammar).unwrap();
+
+        assert_eq!(commit.subject, "subject");
+        assert_eq!(commit.suffix, Some("(#42)"));
+        assert_eq!(commit.subject_span(), (5, 7));
+        assert_eq!(commit.suffix_span(), (13, 5));
+    }
+
+    #[test]
+    fn affixes_are_ignored_without_grammar() {
+        let commit = parse("fix: subject (#42)").unwrap();
+
+        assert_eq!(commit.subject, "subject (#42)");
+        assert_eq!(commit.suffix, None);
+    }
+
+    #[test]
+    fn missing_separator_space_after_prefix_is_detected() {
+        let grammar = Grammar::new(Some("[A-Z]+-[0-9]+"), None).unwrap();
+
+        assert_eq!(
+            missing_separator_space("PROJ-1 feat:subject", &grammar),
+            Some(12)
+        );
+    }
+
+    #[test]
+    fn non_ascii_type_is_parsed() {
+        let commit = parse("fe\u{301}at(ünï): sübject").unwrap();
+
+        assert_eq!(commit.commit_type, "fe\u{301}at");
+        assert_eq!(commit.scope, vec!["ünï"]);
+        assert_eq!(commit.subject, "sübject");
+    }
+
+    #[test]
+    fn spans_are_on_char_boundaries() {
+        let grammar =
+            Grammar::new(Some(r"\p{Extended_Pictographic}"), Some(r"\(№[0-9]+\)")).unwrap();
+        let commit = parse_with("✨ fe\u{301}at(ünï): sübject (№1)\n\nbödy", &grammar).unwrap();
+
+        for (start, len) in [
+            commit.prefix_span(),
+            commit.type_span(),
+            commit.scope_span(),
+            commit.subject_span(),
+            commit.suffix_span(),
+        ] {
+            assert!(commit.source.is_char_boundary(start));
+            assert!(commit.source.is_char_boundary(start + len));
+        }
+
+        let (start, len) = commit.subject_span();
+        assert_eq!(&commit.source[start..start + len], "sübject");
+    }
+
+    #[test]
+    fn breaking_span_points_at_the_marker() {
+        let commit = parse("feat(ui)!: subject").unwrap();
+        assert_eq!(commit.breaking_span(), (8, 1));
+
+        let commit = parse("feat!: subject").unwrap();
+        assert_eq!(commit.breaking_span(), (4, 1));
+
+        let commit = parse("feat: subject\n\nBREAKING CHANGE: it broke").unwrap();
+        assert_eq!(commit.breaking_span(), (15, 15));
+    }
+
+    #[test]
+    fn type_does_not_start_with_a_combining_mark() {
+        assert!(parse("\u{301}feat: subject").is_err());
+    }
+
+    #[test]
+    fn footer_is_recognized() {
+        let grammar = Grammar::default();
+
+        assert!(is_footer("Refs: #123", &grammar));
+        assert!(!is_footer("Refs: ", &grammar));
+        assert!(!is_footer("not a footer", &grammar));
+    }
+
+    #[test]
+    fn hyphenated_breaking_change_parses_to_breaking_change() {
+        let commit = parse("fix: something\n\nBREAKING-CHANGE: yes").unwrap();
+        assert!(commit.breaking_change);
+    }
+
+    #[test]
+    fn misspelled_breaking_change_is_a_footer() {
+        let commit = parse("fix: something\n\nBreaking change: yes").unwrap();
+
+        assert_eq!(commit.footer_value("Breaking change"), Some("yes"));
+        assert_eq!(commit.body, None);
+        assert!(!commit.breaking_change);
+    }
+
     #[test]
     fn no_footer_breaking_change_parses_to_false() {
         let commit = parse("fix: something\n\nChanges were easy").unwrap();